@@ -0,0 +1,30 @@
+use tapestry::{
+    braille::braillify_grid,
+    patterns::{Circle, Line, Rect},
+    VecGrid,
+};
+
+fn main() {
+    let mut grid: VecGrid<bool> = VecGrid::new(Rect::new((41, 41)));
+
+    let circle = Circle::new((20, 20), 18);
+    // Draw circle
+    for (_coord, cell) in grid.selection_iter_mut(circle.iter()).flatten() {
+        *cell = true;
+    }
+
+    // Draw a triangle inside the circle, chaining the edges without drawing the
+    // shared corners twice.
+    let corners = [(20, 4), (34, 28), (6, 28)];
+    for (&from, &to) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+        let edge = Line::new(from, to).iter_exclusive();
+        for (_coord, cell) in grid.selection_iter_mut(edge).flatten() {
+            *cell = true;
+        }
+    }
+
+    // Each character holds a 2x4 block of cells, so the 41x41 grid is printed
+    // as 21x11 characters.
+    let display_grid = braillify_grid(&grid, grid.bounds).map(|cell| cell.to_string());
+    println!("{}", display_grid);
+}
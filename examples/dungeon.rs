@@ -43,15 +43,14 @@ fn main() {
     // room_tree
 
     let cluster = Cluster::new(rooms);
-    for result in grid.selection_iter_mut(cluster.iter_interior()) {
-        if let Ok((_coord, cell)) = result {
-            *cell = Tile::Floor;
-        }
+    for (_coord, cell) in grid.selection_iter_mut(cluster.iter_interior()).flatten() {
+        *cell = Tile::Floor;
     }
-    for result in grid.selection_iter_mut(cluster.iter_internal_border()) {
-        if let Ok((_coord, cell)) = result {
-            *cell = Tile::Wall;
-        }
+    for (_coord, cell) in grid
+        .selection_iter_mut(cluster.iter_internal_border())
+        .flatten()
+    {
+        *cell = Tile::Wall;
     }
 
     // Map grid of characters into a grid of strings, addings spaces between the
@@ -92,15 +91,10 @@ fn shrink_randomly(rect: Rect, min_dimension: i32) -> Rect {
     .translate((new_x, new_y))
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 enum Tile {
+    #[default]
     Void,
     Wall,
     Floor,
 }
-
-impl Default for Tile {
-    fn default() -> Self {
-        Self::Void
-    }
-}
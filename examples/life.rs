@@ -5,10 +5,7 @@ use rand::{
 
 use std::{thread, time::Duration};
 
-use tapestry::{
-    patterns::{Neighborhood, Rect},
-    Coord, VecGrid,
-};
+use tapestry::{patterns::Rect, Coord, VecGrid};
 
 const FPS: f32 = 30.0;
 const FRAME_MILLIS: f32 = 1000.0 / FPS;
@@ -49,29 +46,13 @@ impl LifeBoard {
     }
 
     fn step(&mut self) {
-        let neighbor_counts = self
-            .grid
-            .iter()
-            .map(|cell| self.live_neighbor_count(cell.0))
-            // `collect` to release the borrow on `self`.
-            .collect::<Vec<_>>();
-
-        for ((_coord, cell), neighbor_count) in self.grid.iter_mut().zip(neighbor_counts) {
-            *cell = LifeBoard::compute_state(*cell, neighbor_count)
-        }
-    }
-
-    fn live_neighbor_count(&self, coord: Coord) -> usize {
-        self.grid
-            .selection_iter(Neighborhood::new(coord).iter())
-            .filter(|r_cell| {
-                if let Ok(cell) = r_cell {
-                    *cell.1 == LifeState::Alive
-                } else {
-                    false
-                }
-            })
-            .count()
+        self.grid = self.grid.step(|&cell, neighbors| {
+            let neighbor_count = neighbors
+                .iter()
+                .filter(|&&&neighbor| neighbor == LifeState::Alive)
+                .count();
+            LifeBoard::compute_state(cell, neighbor_count)
+        });
     }
 
     fn compute_state(state: LifeState, neighbor_count: usize) -> LifeState {
@@ -94,18 +75,13 @@ impl LifeBoard {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 enum LifeState {
+    #[default]
     Dead,
     Alive,
 }
 
-impl Default for LifeState {
-    fn default() -> Self {
-        LifeState::Dead
-    }
-}
-
 // Allows us to randomly generate LifeState values.
 impl Distribution<LifeState> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> LifeState {
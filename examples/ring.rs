@@ -7,18 +7,14 @@ fn main() {
     let mut grid: VecGrid<char> = VecGrid::new(Rect::new((19, 19)));
 
     // Fill grid background
-    for (_coord, cell) in grid.iter_mut() {
-        *cell = '∙';
-    }
+    grid.fill('∙');
 
     let external_circle = Circle::new((9, 9), 7);
     let internal_circle = Circle::new((9, 9), 3);
     let ring_coords = external_circle.iter().chain(internal_circle.iter());
     // Draw ring outline
-    for result in grid.selection_iter_mut(ring_coords) {
-        if let Ok((_coord, cell)) = result {
-            *cell = '#';
-        }
+    for (_coord, cell) in grid.selection_iter_mut(ring_coords).flatten() {
+        *cell = '#';
     }
 
     let flood_coords = grid
@@ -28,14 +24,12 @@ fn main() {
         .collect::<Vec<_>>()
         .into_iter();
     // Fill ring
-    for result in grid.selection_iter_mut(flood_coords) {
-        if let Ok((_coord, cell)) = result {
-            *cell = '/';
-        }
+    for (_coord, cell) in grid.selection_iter_mut(flood_coords).flatten() {
+        *cell = '/';
     }
 
     // Map grid of characters into a grid of strings, addings spaces between the
     // characters.
-    let display_grid = grid.map(|cell| format!("{} ", cell.to_string()));
+    let display_grid = grid.map(|cell| format!("{} ", cell));
     println!("{}", display_grid);
 }
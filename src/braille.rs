@@ -0,0 +1,87 @@
+//! Renders bit grids as text with Unicode braille patterns, packing each 2x4
+//! block of cells into a single character.
+
+use crate::{coord::Coord, grid::Grid, patterns::Rect, vecgrid::VecGrid};
+
+/// The first braille pattern character, with no dots raised.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Returns the bit for the dot at `offset` within a 2x4 braille block, where
+/// `y = 0` is the top row.
+fn dot_bit(offset: Coord) -> u32 {
+    match (offset.x, offset.y) {
+        (0, 3) => 1 << 6,
+        (1, 3) => 1 << 7,
+        (x, y) => 1 << (3 * x + y),
+    }
+}
+
+/// Packs each 2x4 block of `grid` within `bounds` into a braille character,
+/// with `true` cells as raised dots. The top row of `bounds` is the top row of
+/// dots.
+///
+/// The result is half as wide and a quarter as tall as `bounds`, rounding up,
+/// so the trailing blocks on the right and bottom edges may be partial. Cells
+/// that `grid` has no value for are treated as `false`. To braillify a whole
+/// [`VecGrid`], pass its own bounds.
+pub fn braillify_grid<G: Grid<bool>>(grid: &G, bounds: Rect) -> VecGrid<char> {
+    let dimensions = bounds.dimensions();
+    let output_bounds = Rect::new(((dimensions.x + 1) / 2, (dimensions.y + 3) / 4));
+    VecGrid::with_generator(output_bounds, |block: Coord| {
+        let corner = bounds.offset() + Coord::new(block.x * 2, block.y * 4);
+        let dots = Rect::with_corners(corner, corner + Coord::new(2, 4))
+            .iter()
+            .filter(|&coord| bounds.contains(coord) && grid.get(coord) == Some(&true))
+            .fold(0, |dots, coord| dots | dot_bit(coord - corner));
+        std::char::from_u32(BRAILLE_BLANK + dots).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sparsegrid::SparseGrid;
+
+    use super::*;
+
+    #[test]
+    fn braillify_aligned_grid() {
+        let mut grid = VecGrid::<bool>::new(Rect::with_corners((-2, 3), (2, 7)));
+        assert!(braillify_grid(&grid, grid.bounds)
+            .iter()
+            .all(|(_coord, &braille)| braille == '\u{2800}'));
+        grid.set((-2, 3), true);
+        grid.set((-1, 6), true);
+        grid.set((0, 4), true);
+        let braille = braillify_grid(&grid, grid.bounds);
+        assert_eq!(braille.bounds, Rect::new((2, 1)));
+        assert_eq!(braille.get((0, 0)), Some(&'\u{2881}'));
+        assert_eq!(braille.get((1, 0)), Some(&'\u{2802}'));
+    }
+
+    #[test]
+    fn braillify_partial_blocks() {
+        let grid = VecGrid::with_generator(Rect::new((3, 5)), |_: Coord| true);
+        let braille = braillify_grid(&grid, grid.bounds);
+        assert_eq!(braille.bounds, Rect::new((2, 2)));
+        assert_eq!(braille.get((0, 0)), Some(&'⣿'));
+        assert_eq!(braille.get((1, 0)), Some(&'⡇'));
+        assert_eq!(braille.get((0, 1)), Some(&'⠉'));
+        assert_eq!(braille.get((1, 1)), Some(&'⠁'));
+    }
+
+    #[test]
+    fn braillify_sparse_grid() {
+        let mut grid = SparseGrid::<bool>::new();
+        for y in 0..4 {
+            grid.set((10, y), true);
+        }
+        grid.set((11, 0), true);
+        let braille = braillify_grid(&grid, grid.bounds().unwrap());
+        assert_eq!(braille.bounds, Rect::new((1, 1)));
+        assert_eq!(braille.get((0, 0)), Some(&'\u{284F}'));
+
+        // Cells of `grid` outside of `bounds` are ignored.
+        let cropped = braillify_grid(&grid, Rect::with_corners((10, 0), (11, 2)));
+        assert_eq!(cropped.get((0, 0)), Some(&'\u{2803}'));
+    }
+}
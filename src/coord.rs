@@ -6,6 +6,7 @@ use std::{
 
 /// The coordinate key to a specific [`Grid`](crate::grid::Grid) cell.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coord {
     pub x: i32,
     pub y: i32,
@@ -48,8 +49,92 @@ impl Coord {
     pub const fn negate_y(&self) -> Self {
         Self::new(self.x, -self.y)
     }
+
+    /// Reflect over the vertical line at `x`.
+    pub const fn reflect_across_x(&self, x: i32) -> Self {
+        Self::new(2 * x - self.x, self.y)
+    }
+
+    /// Reflect over the horizontal line at `y`.
+    pub const fn reflect_across_y(&self, y: i32) -> Self {
+        Self::new(self.x, 2 * y - self.y)
+    }
+
+    /// Rotate a quarter turn clockwise about the origin, such that `NORTH`
+    /// becomes `EAST`.
+    pub const fn rotate_cw(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Rotate a quarter turn counterclockwise about the origin, such that
+    /// `NORTH` becomes `WEST`.
+    pub const fn rotate_ccw(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Linearly interpolates between `self` at `t = 0.0` and `other` at
+    /// `t = 1.0`, rounding each component to the nearest integer (with halves
+    /// rounded away from zero). Values of `t` outside of `[0, 1]` extrapolate
+    /// beyond the two coords.
+    pub fn lerp(&self, other: Coord, t: f32) -> Coord {
+        let lerp = |a: i32, b: i32| (a as f32 + (b - a) as f32 * t).round() as i32;
+        Coord::new(lerp(self.x, other.x), lerp(self.y, other.y))
+    }
+
+    /// The number of orthogonal steps between two coords.
+    pub const fn manhattan_distance(&self, other: Coord) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The number of steps between two coords when diagonal steps are allowed.
+    pub const fn chebyshev_distance(&self, other: Coord) -> i32 {
+        let dx = (self.x - other.x).abs();
+        let dy = (self.y - other.y).abs();
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
 }
 
+/// A row index (a `y` coordinate), used to keep row and column arguments from
+/// being mixed up.
+///
+/// ```compile_fail
+/// use tapestry::{patterns::Rect, Col, VecGrid};
+///
+/// let grid = VecGrid::<()>::new(Rect::new((2, 2)));
+/// // A `Col` can't be passed where a `Row` is expected.
+/// grid.row(Col(0));
+/// ```
+///
+/// Nor can a plain `i32`, which might be an `x` coordinate. Use
+/// [`VecGrid::row_at`](crate::VecGrid::row_at) to pass one deliberately.
+///
+/// ```compile_fail
+/// use tapestry::{patterns::Rect, VecGrid};
+///
+/// let grid = VecGrid::<()>::new(Rect::new((2, 2)));
+/// let x = 0;
+/// grid.row(x);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Row(pub i32);
+
+/// A column index (an `x` coordinate), used to keep row and column arguments
+/// from being mixed up.
+///
+/// ```compile_fail
+/// use tapestry::{patterns::Rect, Row, VecGrid};
+///
+/// let grid = VecGrid::<()>::new(Rect::new((2, 2)));
+/// // A `Row` can't be passed where a `Col` is expected.
+/// grid.column(Row(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Col(pub i32);
+
 impl Add<Coord> for Coord {
     type Output = Coord;
 
@@ -95,6 +180,29 @@ impl MulAssign<Coord> for Coord {
     }
 }
 
+impl Mul<i32> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Mul<Coord> for i32 {
+    type Output = Coord;
+
+    fn mul(self, rhs: Coord) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl MulAssign<i32> for Coord {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
 impl From<(i32, i32)> for Coord {
     fn from((x, y): (i32, i32)) -> Self {
         Coord::new(x, y)
@@ -178,6 +286,87 @@ mod tests {
         assert!(newline_coord_str.parse::<Coord>() == Err(ParseCoordError::InvalidDigit));
     }
 
+    #[test]
+    fn reflect_across_vertical_line() {
+        assert_eq!(Coord::new(2, 7).reflect_across_x(5), Coord::new(8, 7));
+        assert_eq!(Coord::new(5, 1).reflect_across_x(5), Coord::new(5, 1));
+    }
+
+    #[test]
+    fn reflect_across_horizontal_line() {
+        assert_eq!(Coord::new(4, 0).reflect_across_y(-3), Coord::new(4, -6));
+        assert_eq!(Coord::new(4, -5).reflect_across_y(-3), Coord::new(4, -1));
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        assert_eq!(Coord::NORTH_EAST * 3, Coord::new(3, 3));
+        assert_eq!(-2 * Coord::NORTH_EAST, Coord::new(-2, -2));
+        let mut coord = Coord::new(1, -4);
+        coord *= -3;
+        assert_eq!(coord, Coord::new(-3, 12));
+    }
+
+    #[test]
+    fn rotate_directions() {
+        assert_eq!(Coord::NORTH.rotate_cw(), Coord::EAST);
+        assert_eq!(Coord::EAST.rotate_cw(), Coord::SOUTH);
+        assert_eq!(Coord::NORTH.rotate_ccw(), Coord::WEST);
+        assert_eq!(Coord::NORTH_EAST.rotate_ccw(), Coord::NORTH_WEST);
+    }
+
+    #[test]
+    fn four_rotations_are_identity() {
+        for &coord in [Coord::new(3, -2), Coord::new(0, 5), Coord::ZERO].iter() {
+            let rotated = coord.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+            assert_eq!(rotated, coord);
+            assert_eq!(coord.rotate_cw().rotate_ccw(), coord);
+        }
+    }
+
+    #[test]
+    fn lerp_endpoints() {
+        let a = Coord::new(-2, 5);
+        let b = Coord::new(4, -1);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 2.0), Coord::new(10, -7));
+    }
+
+    #[test]
+    fn lerp_rounds_halves_away_from_zero() {
+        assert_eq!(Coord::ZERO.lerp(Coord::new(3, -3), 0.5), Coord::new(2, -2));
+        assert_eq!(
+            Coord::new(1, 1).lerp(Coord::new(4, 2), 0.5),
+            Coord::new(3, 2)
+        );
+    }
+
+    #[test]
+    fn axis_aligned_distances() {
+        let a = Coord::new(-2, 3);
+        let b = Coord::new(4, 3);
+        assert_eq!(a.manhattan_distance(b), 6);
+        assert_eq!(a.chebyshev_distance(b), 6);
+    }
+
+    #[test]
+    fn diagonal_distances() {
+        let a = Coord::new(1, 1);
+        let b = Coord::new(-2, 5);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(b.manhattan_distance(a), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(b.chebyshev_distance(a), 4);
+    }
+
+    #[test]
+    fn same_point_distances() {
+        let a = Coord::new(7, -7);
+        assert_eq!(a.manhattan_distance(a), 0);
+        assert_eq!(a.chebyshev_distance(a), 0);
+    }
+
     #[test]
     fn coord_parse_invalid_dimensions() {
         let insufficient_coord_str = "(0)";
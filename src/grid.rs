@@ -1,6 +1,6 @@
-use std::mem;
+use std::{error::Error, fmt, mem};
 
-use crate::coord::Coord;
+use crate::{coord::Coord, patterns::Neighborhood};
 
 /// The return type of all Grid iterators; a tuple of the cell coordinate and a
 /// reference to the cell data.
@@ -15,16 +15,54 @@ pub enum GridError {
     /// The coordinate has no cell associated with it, as it's out of the grid
     /// bounds.
     OutOfBounds(Coord),
+    /// The source coordinate of a copy is out of the grid bounds.
+    SourceOutOfBounds(Coord),
+    /// The destination coordinate of a copy is out of the grid bounds.
+    DestOutOfBounds(Coord),
+    /// The source and destination of a copy are the same coordinate, so the
+    /// copy would do nothing.
+    SameCoord(Coord),
     /// The coordinate has previously been mutably borrowed from the iterator,
     /// and doing so again would break safety guarantees.
     AlreadyVisited(Coord),
+    /// The bounds don't describe the given cell data, such as when the number
+    /// of cells doesn't match the area of the bounds.
+    InvalidBounds,
+    /// The input couldn't be parsed into a grid.
+    InvalidFormat,
 }
 
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::OutOfBounds(coord) => write!(f, "coord {} is out of bounds", coord),
+            GridError::SourceOutOfBounds(coord) => {
+                write!(f, "source coord {} is out of bounds", coord)
+            }
+            GridError::DestOutOfBounds(coord) => {
+                write!(f, "destination coord {} is out of bounds", coord)
+            }
+            GridError::SameCoord(coord) => {
+                write!(f, "source and destination are both coord {}", coord)
+            }
+            GridError::AlreadyVisited(coord) => write!(f, "coord {} was already visited", coord),
+            GridError::InvalidBounds => write!(f, "bounds don't match the cell data"),
+            GridError::InvalidFormat => write!(f, "invalid grid format"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
 pub trait Grid<T> {
     fn get<C: Into<Coord>>(&self, coord: C) -> Option<&T>;
 
     fn get_mut<C: Into<Coord>>(&mut self, coord: C) -> Option<&mut T>;
 
+    /// Copies the contents of `src` into `dest`, returning `false` if either
+    /// coord is out of bounds, or if they're the same coord, since copying a
+    /// cell onto itself does nothing. Use [`try_copy`](Self::try_copy) to find
+    /// out which of these went wrong.
     fn copy<C1, C2>(&mut self, src: C1, dest: C2) -> bool
     where
         T: Copy,
@@ -37,11 +75,15 @@ pub trait Grid<T> {
         C1: Into<Coord>,
         C2: Into<Coord>;
 
-    /// Moves the contents of `src` into `dest`, returning the previous contents
-    /// of `dest`.
+    /// Moves the contents of `src` into `dest`, leaving the default value in
+    /// `src` and returning the previous contents of `dest`, or `None` if either
+    /// coord is out of bounds.
+    ///
+    /// Moving a cell onto itself leaves it unchanged, returning a clone of its
+    /// existing contents.
     fn mov(&mut self, src: Coord, dest: Coord) -> Option<T>
     where
-        T: Default;
+        T: Default + Clone;
 
     //
     // DEFAULT IMPLEMENTATIONS
@@ -57,14 +99,79 @@ pub trait Grid<T> {
     }
 
     fn replace<C: Into<Coord>>(&mut self, coord: C, value: T) -> Option<T> {
-        self.get_mut(coord)
-            .and_then(|cell| Some(mem::replace(cell, value)))
+        self.get_mut(coord).map(|cell| mem::replace(cell, value))
     }
 
     fn take<C: Into<Coord>>(&mut self, coord: C) -> Option<T>
     where
         T: Default,
     {
-        self.get_mut(coord).and_then(|cell| Some(mem::take(cell)))
+        self.get_mut(coord).map(mem::take)
+    }
+
+    /// Counts the Moore neighbors of `coord` that satisfy `predicate`.
+    /// Neighbors that [`get`](Self::get) has no cell for, such as those out of
+    /// bounds, never match.
+    fn neighbors_matching<C: Into<Coord>>(
+        &self,
+        coord: C,
+        predicate: impl Fn(&T) -> bool,
+    ) -> usize {
+        Neighborhood::new(coord)
+            .into_iter()
+            .filter(|&neighbor| self.get(neighbor).is_some_and(&predicate))
+            .count()
+    }
+
+    /// Like [`copy`](Self::copy), but returns an error describing why the copy
+    /// failed.
+    ///
+    /// The checks are made in order: an out of bounds `src` gives
+    /// [`GridError::SourceOutOfBounds`](GridError::SourceOutOfBounds), then an
+    /// out of bounds `dest` gives
+    /// [`GridError::DestOutOfBounds`](GridError::DestOutOfBounds), then equal
+    /// coords give [`GridError::SameCoord`](GridError::SameCoord). So if both
+    /// coords are out of bounds, only `src` is reported.
+    fn try_copy<C1, C2>(&mut self, src: C1, dest: C2) -> Result<(), GridError>
+    where
+        T: Copy,
+        C1: Into<Coord>,
+        C2: Into<Coord>,
+    {
+        let (src, dest) = (src.into(), dest.into());
+        let value = *self.get(src).ok_or(GridError::SourceOutOfBounds(src))?;
+        if self.get(dest).is_none() {
+            return Err(GridError::DestOutOfBounds(dest));
+        }
+        if src == dest {
+            return Err(GridError::SameCoord(src));
+        }
+        self.set(dest, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{patterns::Rect, VecGrid};
+
+    use super::*;
+
+    #[test]
+    fn surrounded_cell_neighbors_matching() {
+        let mut grid = VecGrid::with_generator(Rect::new((3, 3)), |_: Coord| true);
+        assert_eq!(grid.neighbors_matching((1, 1), |&cell| cell), 8);
+        grid.set((0, 2), false);
+        grid.set((1, 1), false);
+        assert_eq!(grid.neighbors_matching((1, 1), |&cell| cell), 7);
+        assert_eq!(grid.neighbors_matching((1, 1), |&cell| !cell), 1);
+    }
+
+    #[test]
+    fn corner_cell_neighbors_matching() {
+        let grid = VecGrid::with_generator(Rect::new((3, 3)), |_: Coord| true);
+        assert_eq!(grid.neighbors_matching((0, 0), |&cell| cell), 3);
+        assert_eq!(grid.neighbors_matching((2, 2), |&cell| cell), 3);
+        assert_eq!(grid.neighbors_matching((-1, -1), |&cell| cell), 1);
     }
 }
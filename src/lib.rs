@@ -1,9 +1,13 @@
+pub mod braille;
+pub mod pathfinding;
 pub mod patterns;
 
 mod coord;
 mod grid;
+mod sparsegrid;
 mod vecgrid;
 
-pub use coord::{Coord, ParseCoordError};
+pub use coord::{Col, Coord, ParseCoordError, Row};
 pub use grid::{Grid, GridError, IterCell, IterCellMut};
-pub use vecgrid::{FloodIter, SelectionIter, SelectionIterMut, VecGrid};
+pub use sparsegrid::SparseGrid;
+pub use vecgrid::{EdgeMode, FloodIter, FloodOptions, SelectionIter, SelectionIterMut, VecGrid};
@@ -0,0 +1,801 @@
+//! Shortest path searches and line of sight queries over
+//! [`VecGrid`](crate::VecGrid) cells.
+//!
+//! All path searches move between orthogonally adjacent cells, treating cells
+//! for which the `passable` predicate returns `false` as walls.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::{
+    coord::Coord,
+    grid::Grid,
+    patterns::{FilledCircle, Line, Neighborhood, Rect},
+    vecgrid::VecGrid,
+};
+
+impl<T> VecGrid<T> {
+    /// Finds a shortest 4-connected path from `start` to `goal` using A* with
+    /// a Manhattan distance heuristic. The returned path includes both `start`
+    /// and `goal`.
+    ///
+    /// Returns `None` if no path exists, including when `start` or `goal` is
+    /// out of bounds or impassable.
+    pub fn astar<C: Into<Coord>>(
+        &self,
+        start: C,
+        goal: C,
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<Vec<Coord>> {
+        self.astar_with(start, goal, passable, TieBreaking::Arbitrary)
+    }
+
+    /// Like [`astar`](Self::astar), but with control over which of several
+    /// equally short paths is returned.
+    pub fn astar_with<C: Into<Coord>>(
+        &self,
+        start: C,
+        goal: C,
+        passable: impl Fn(&T) -> bool,
+        tie_breaking: TieBreaking,
+    ) -> Option<Vec<Coord>> {
+        let start = start.into();
+        let goal = goal.into();
+        let is_passable =
+            |coord: Coord| self.bounds.contains(coord) && self.get(coord).is_some_and(&passable);
+        if !is_passable(start) || !is_passable(goal) {
+            return None;
+        }
+
+        // Turns can only be counted if the direction a cell was entered from
+        // is part of the search state, since the same cell may be reached
+        // along different directions at equal cost.
+        let start_state = SearchState {
+            coord: start,
+            direction: None,
+        };
+        let mut open_nodes = BinaryHeap::new();
+        let mut costs = HashMap::new();
+        let mut came_from = HashMap::new();
+        open_nodes.push(SearchNode {
+            estimate: manhattan_distance(start, goal),
+            turns: 0,
+            cost: 0,
+            state: start_state,
+        });
+        costs.insert(start_state, (0, 0));
+
+        while let Some(SearchNode {
+            turns, cost, state, ..
+        }) = open_nodes.pop()
+        {
+            if state.coord == goal {
+                return Some(
+                    reconstruct_path(&came_from, state)
+                        .into_iter()
+                        .map(|state| state.coord)
+                        .collect(),
+                );
+            }
+            // Skip stale entries superseded by a cheaper route.
+            if (cost, turns) > costs[&state] {
+                continue;
+            }
+            for neighbor in Neighborhood::new(state.coord).iter_ortho() {
+                if !is_passable(neighbor) {
+                    continue;
+                }
+                let direction = neighbor - state.coord;
+                let (neighbor_state, neighbor_turns) = match tie_breaking {
+                    TieBreaking::Arbitrary => (
+                        SearchState {
+                            coord: neighbor,
+                            direction: None,
+                        },
+                        0,
+                    ),
+                    TieBreaking::FewestTurns => (
+                        SearchState {
+                            coord: neighbor,
+                            direction: Some(direction),
+                        },
+                        turns + state.direction.is_some_and(|d| d != direction) as u32,
+                    ),
+                };
+                let neighbor_cost = cost + 1;
+                if costs
+                    .get(&neighbor_state)
+                    .is_some_and(|&previous_cost| previous_cost <= (neighbor_cost, neighbor_turns))
+                {
+                    continue;
+                }
+                costs.insert(neighbor_state, (neighbor_cost, neighbor_turns));
+                came_from.insert(neighbor_state, state);
+                open_nodes.push(SearchNode {
+                    estimate: neighbor_cost + manhattan_distance(neighbor, goal),
+                    turns: neighbor_turns,
+                    cost: neighbor_cost,
+                    state: neighbor_state,
+                });
+            }
+        }
+        None
+    }
+
+    /// Computes the number of 4-connected steps from each cell to the nearest
+    /// of `sources`, such as for flow fields.
+    ///
+    /// The resulting grid shares the bounds of `self`. Cells that are
+    /// impassable or unreachable from every source are `None`, and sources
+    /// that are themselves out of bounds or impassable are ignored.
+    pub fn distance_field<C: Into<Coord>>(
+        &self,
+        sources: impl Iterator<Item = C>,
+        passable: impl Fn(&T) -> bool,
+    ) -> VecGrid<Option<u32>> {
+        let mut distances = self.map(|_| None);
+        let mut coords_to_search = VecDeque::new();
+        for source in sources.map(Into::into) {
+            if self.get(source).is_some_and(&passable) && distances.set(source, Some(0)) {
+                coords_to_search.push_back((source, 0));
+            }
+        }
+
+        while let Some((coord, distance)) = coords_to_search.pop_front() {
+            for neighbor in Neighborhood::new(coord).iter_ortho() {
+                if distances.get(neighbor) != Some(&None)
+                    || !self.get(neighbor).is_some_and(&passable)
+                {
+                    continue;
+                }
+                distances.set(neighbor, Some(distance + 1));
+                coords_to_search.push_back((neighbor, distance + 1));
+            }
+        }
+        distances
+    }
+
+    /// Estimates the diameter of the largest 4-connected passable region: the
+    /// greatest number of steps along the shortest path between any two of its
+    /// cells.
+    ///
+    /// This uses the double breadth-first search trick, searching once from an
+    /// arbitrary cell and again from the farthest cell found. The result is
+    /// exact for regions without loops, such as corridors and mazes, and a
+    /// lower bound otherwise. Returns `None` if no cells are passable.
+    pub fn region_diameter(&self, passable: impl Fn(&T) -> bool) -> Option<u32> {
+        let region = self
+            .connected_components(&passable)
+            .into_iter()
+            .max_by_key(|region| region.0.len())?;
+        let start = region.0.iter().min_by_key(|coord| (coord.y, coord.x))?;
+        let farthest_from = |source: Coord| {
+            self.distance_field(std::iter::once(source), &passable)
+                .iter()
+                .filter_map(|(coord, distance)| distance.map(|distance| (coord, distance)))
+                .max_by_key(|&(_coord, distance)| distance)
+        };
+        let (end, _distance) = farthest_from(*start)?;
+        farthest_from(end).map(|(_coord, distance)| distance)
+    }
+}
+
+/// Finds the cheapest 4-connected path from `start` to `goal`, where entering
+/// a cell costs one step plus the value of `danger` at that cell, so the path
+/// detours around dangerous areas when that's cheaper than crossing them. The
+/// returned path includes both `start` and `goal`.
+///
+/// Path costs saturate at `u32::MAX` rather than overflowing, so a danger of
+/// `u32::MAX` marks a cell to avoid whenever any other path exists. A path
+/// through such a cell is still found if it's the only one, but it may not be
+/// the cheapest, since all saturated paths cost the same.
+///
+/// Returns `None` if no path exists, including when `start` or `goal` is out
+/// of bounds or impassable, or if `danger` doesn't share the bounds of `grid`.
+pub fn astar_avoiding<T>(
+    grid: &VecGrid<T>,
+    start: Coord,
+    goal: Coord,
+    passable: impl Fn(&T) -> bool,
+    danger: &VecGrid<u32>,
+) -> Option<Vec<Coord>> {
+    if danger.bounds != grid.bounds {
+        return None;
+    }
+    let is_passable = |coord: Coord| grid.get(coord).is_some_and(&passable);
+    if !is_passable(start) || !is_passable(goal) {
+        return None;
+    }
+
+    let mut open_nodes = BinaryHeap::new();
+    let mut costs = HashMap::new();
+    let mut came_from = HashMap::new();
+    open_nodes.push(SearchNode {
+        estimate: manhattan_distance(start, goal),
+        turns: 0,
+        cost: 0,
+        state: SearchState {
+            coord: start,
+            direction: None,
+        },
+    });
+    costs.insert(start, 0);
+
+    while let Some(SearchNode { cost, state, .. }) = open_nodes.pop() {
+        let coord = state.coord;
+        if coord == goal {
+            return Some(reconstruct_path(&came_from, goal));
+        }
+        // Skip stale entries superseded by a cheaper route.
+        if cost > costs[&coord] {
+            continue;
+        }
+        for neighbor in Neighborhood::new(coord).iter_ortho() {
+            if !is_passable(neighbor) {
+                continue;
+            }
+            // The step itself keeps the Manhattan heuristic admissible.
+            let neighbor_cost = cost
+                .saturating_add(1)
+                .saturating_add(*danger.get(neighbor).unwrap());
+            if costs
+                .get(&neighbor)
+                .is_some_and(|&previous_cost| previous_cost <= neighbor_cost)
+            {
+                continue;
+            }
+            costs.insert(neighbor, neighbor_cost);
+            came_from.insert(neighbor, coord);
+            open_nodes.push(SearchNode {
+                estimate: neighbor_cost.saturating_add(manhattan_distance(neighbor, goal)),
+                turns: 0,
+                cost: neighbor_cost,
+                state: SearchState {
+                    coord: neighbor,
+                    direction: None,
+                },
+            });
+        }
+    }
+    None
+}
+
+/// The most paths [`all_shortest_paths`] returns, since the number of shortest
+/// paths grows combinatorially; an open 12x12 room already has 705,432 between
+/// opposite corners.
+pub const MAX_SHORTEST_PATHS: usize = 1024;
+
+/// Returns every distinct 4-connected path from `start` to `goal` with the
+/// fewest steps, each including both `start` and `goal`, up to
+/// [`MAX_SHORTEST_PATHS`] of them.
+///
+/// Returns no paths if `goal` is unreachable, including when `start` or `goal`
+/// is out of bounds or impassable.
+pub fn all_shortest_paths<T>(
+    grid: &VecGrid<T>,
+    start: Coord,
+    goal: Coord,
+    passable: impl Fn(&T) -> bool,
+) -> Vec<Vec<Coord>> {
+    let distances = grid.distance_field(Some(goal).into_iter(), &passable);
+    let mut paths = Vec::new();
+    if !grid.get(start).is_some_and(&passable) || distances.get(start) == Some(&None) {
+        return paths;
+    }
+
+    // Walk from `start` towards `goal`, only ever stepping one closer to it.
+    let mut path = vec![start];
+    let mut untried_steps = vec![steps_closer(&distances, start)];
+    while let Some(steps) = untried_steps.last_mut() {
+        let coord = *path.last().unwrap();
+        if coord == goal {
+            paths.push(path.clone());
+            if paths.len() == MAX_SHORTEST_PATHS {
+                break;
+            }
+        }
+        match steps.pop() {
+            Some(next) => {
+                path.push(next);
+                untried_steps.push(steps_closer(&distances, next));
+            }
+            None => {
+                path.pop();
+                untried_steps.pop();
+            }
+        }
+    }
+    paths
+}
+
+/// The orthogonal neighbors of `coord` one step closer to the source of
+/// `distances`, reversed so they're popped in [`Neighborhood::iter_ortho`]
+/// order.
+fn steps_closer(distances: &VecGrid<Option<u32>>, coord: Coord) -> Vec<Coord> {
+    let distance = distances.get(coord).copied().flatten();
+    let mut steps = Neighborhood::new(coord)
+        .iter_ortho()
+        .filter(|&neighbor| {
+            distance.is_some_and(|distance| {
+                distance > 0 && distances.get(neighbor) == Some(&Some(distance - 1))
+            })
+        })
+        .collect::<Vec<_>>();
+    steps.reverse();
+    steps
+}
+
+/// Finds a closed patrol route visiting each of `waypoints`, starting and
+/// ending at the first one.
+///
+/// The waypoints are ordered with the nearest neighbor heuristic, always
+/// heading to the closest unvisited waypoint by path length, and the
+/// [`astar`](VecGrid::astar) paths between them are joined into one tour. This
+/// is fast but not always the shortest tour. Returns `None` if any waypoint is
+/// unreachable from the others.
+pub fn waypoint_tour<T>(
+    grid: &VecGrid<T>,
+    waypoints: &[Coord],
+    passable: impl Fn(&T) -> bool,
+) -> Option<Vec<Coord>> {
+    let first = match waypoints.first() {
+        Some(&first) => first,
+        None => return Some(vec![]),
+    };
+    if !grid.get(first).is_some_and(&passable) {
+        return None;
+    }
+
+    let mut paths = HashMap::new();
+    for (i, &a) in waypoints.iter().enumerate() {
+        for (j, &b) in waypoints.iter().enumerate().skip(i + 1) {
+            let path = grid.astar(a, b, &passable)?;
+            paths.insert((j, i), path.iter().rev().copied().collect::<Vec<_>>());
+            paths.insert((i, j), path);
+        }
+    }
+
+    let mut unvisited = (1..waypoints.len()).collect::<Vec<_>>();
+    let mut current = 0;
+    let mut tour = vec![first];
+    while !unvisited.is_empty() {
+        let (position, &next) = unvisited
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_position, &next)| paths[&(current, next)].len())
+            .unwrap();
+        unvisited.swap_remove(position);
+        tour.extend(paths[&(current, next)].iter().skip(1));
+        current = next;
+    }
+    if current != 0 {
+        tour.extend(paths[&(current, 0)].iter().skip(1));
+    }
+    Some(tour)
+}
+
+/// Covers the walkable cells of `grid` with non-overlapping rects, for
+/// pathfinding between the rects rather than individual cells. Rects that
+/// share an edge are connected in the navmesh.
+///
+/// Rects are made greedily: starting from the first uncovered walkable cell in
+/// row-major order, each extends as far right as it can, then as far down as
+/// every cell of its width allows. This is fast but doesn't always find the
+/// fewest rects.
+pub fn navmesh<T>(grid: &VecGrid<T>, walkable: impl Fn(&T) -> bool) -> Vec<Rect> {
+    let mut uncovered = grid.map(&walkable);
+    let mut rects = vec![];
+    for corner in grid.iter().map(|(coord, _cell)| coord) {
+        if uncovered.get(corner) != Some(&true) {
+            continue;
+        }
+        let mut right = corner.x + 1;
+        while uncovered.get((right, corner.y)) == Some(&true) {
+            right += 1;
+        }
+        let mut bottom = corner.y + 1;
+        while (corner.x..right).all(|x| uncovered.get((x, bottom)) == Some(&true)) {
+            bottom += 1;
+        }
+        let rect = Rect::with_corners(corner, (right, bottom));
+        uncovered.fill_rect(rect, false);
+        rects.push(rect);
+    }
+    rects
+}
+
+/// Returns the in-bounds coords within `radius` (by Euclidean distance) of
+/// `origin` that are visible from it, in row-major order.
+///
+/// A coord is visible if the Bresenham [`Line`] to it doesn't pass through any
+/// cell for which `blocks` returns `true`. Blocking cells can be seen
+/// themselves, like the walls of a room, but hide everything behind them.
+pub fn field_of_view<T>(
+    grid: &VecGrid<T>,
+    origin: Coord,
+    radius: i32,
+    blocks: impl Fn(&T) -> bool,
+) -> Vec<Coord> {
+    if grid.get(origin).is_none() {
+        return vec![];
+    }
+    FilledCircle::new(origin, radius)
+        .iter()
+        .filter(|&target| grid.get(target).is_some())
+        .filter(|&target| {
+            Line::new(origin, target)
+                .iter()
+                .skip(1)
+                .take_while(|&coord| coord != target)
+                .all(|coord| !grid.get(coord).is_some_and(&blocks))
+        })
+        .collect()
+}
+
+/// Counts how many other cells each cell can see within `radius` (see
+/// [`field_of_view`]). The cell with the highest count is the best vantage
+/// point. Blocking cells have a count of 0.
+pub fn visibility_map<T>(
+    grid: &VecGrid<T>,
+    radius: i32,
+    blocks: impl Fn(&T) -> bool,
+) -> VecGrid<u32> {
+    VecGrid::with_generator(grid.bounds, |coord: Coord| {
+        if grid.get(coord).is_some_and(&blocks) {
+            return 0;
+        }
+        // Exclude the cell itself, which isn't in view with a negative radius.
+        (field_of_view(grid, coord, radius, &blocks).len() as u32).saturating_sub(1)
+    })
+}
+
+/// Chooses between paths of equal length in [`VecGrid::astar_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreaking {
+    /// Returns whichever shortest path is found first, which on open ground
+    /// tends to zigzag.
+    Arbitrary,
+    /// Returns the shortest path with the fewest changes of direction. Path
+    /// length always takes priority, so this never produces a longer path.
+    FewestTurns,
+}
+
+fn manhattan_distance(a: Coord, b: Coord) -> u32 {
+    a.manhattan_distance(b) as u32
+}
+
+/// Walks `came_from` backwards from `goal` to build the path to it.
+fn reconstruct_path<K: Copy + Eq + Hash>(came_from: &HashMap<K, K>, goal: K) -> Vec<K> {
+    let mut path = vec![goal];
+    let mut coord = goal;
+    while let Some(&previous) = came_from.get(&coord) {
+        path.push(previous);
+        coord = previous;
+    }
+    path.reverse();
+    path
+}
+
+/// A cell of the A* search, along with the direction it was entered from when
+/// that matters for tie-breaking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SearchState {
+    coord: Coord,
+    direction: Option<Coord>,
+}
+
+impl SearchState {
+    fn sort_key(&self) -> (i32, i32, Option<(i32, i32)>) {
+        (
+            self.coord.x,
+            self.coord.y,
+            self.direction.map(|direction| (direction.x, direction.y)),
+        )
+    }
+}
+
+/// An entry in the A* open set, ordered so that `BinaryHeap` pops the lowest
+/// estimated total cost first.
+#[derive(Debug, PartialEq, Eq)]
+struct SearchNode {
+    /// The cost so far plus the heuristic estimate of the remaining cost.
+    estimate: u32,
+    /// The number of changes of direction so far, which is always 0 unless
+    /// tie-breaking by [`TieBreaking::FewestTurns`].
+    turns: u32,
+    cost: u32,
+    state: SearchState,
+}
+
+impl Ord for SearchNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| other.turns.cmp(&self.turns))
+            // Among equal estimates, prefer nodes closer to the goal.
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| other.state.sort_key().cmp(&self.state.sort_key()))
+    }
+}
+
+impl PartialOrd for SearchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::patterns::any_overlap;
+
+    use super::*;
+
+    #[test]
+    fn straight_astar_path() {
+        let grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        let path = grid.astar((0, 2), (4, 2), |&wall| !wall).unwrap();
+        assert_eq!(
+            path,
+            Rect::with_corners((0, 2), (5, 3))
+                .iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn astar_path_around_wall() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        for y in 0..4 {
+            grid.set((2, y), true);
+        }
+        let path = grid.astar((0, 0), (4, 0), |&wall| !wall).unwrap();
+        assert_eq!(path.first(), Some(&Coord::new(0, 0)));
+        assert_eq!(path.last(), Some(&Coord::new(4, 0)));
+        // Up and over the wall, and back down again.
+        assert_eq!(path.len(), 13);
+        assert!(path.iter().all(|&coord| grid.get(coord) == Some(&false)));
+        assert!(path
+            .windows(2)
+            .all(|step| step[0].manhattan_distance(step[1]) == 1));
+    }
+
+    #[test]
+    fn unreachable_astar_goal() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        for y in 0..5 {
+            grid.set((2, y), true);
+        }
+        assert_eq!(grid.astar((0, 0), (4, 0), |&wall| !wall), None);
+        assert_eq!(grid.astar((0, 0), (2, 0), |&wall| !wall), None);
+        assert_eq!(grid.astar((0, 0), (9, 0), |&wall| !wall), None);
+    }
+
+    #[test]
+    fn fewest_turns_astar_path() {
+        fn count_turns(path: &[Coord]) -> usize {
+            path.windows(3)
+                .filter(|steps| steps[1] - steps[0] != steps[2] - steps[1])
+                .count()
+        }
+
+        // A mostly open field, with a few obstacles that the default search
+        // weaves between.
+        let mut grid = VecGrid::<bool>::new(Rect::new((6, 6)));
+        for &wall in [(0, 3), (1, 3), (2, 4)].iter() {
+            grid.set(wall, true);
+        }
+        let default_path = grid.astar((0, 0), (5, 5), |&wall| !wall).unwrap();
+        let straight_path = grid
+            .astar_with((0, 0), (5, 5), |&wall| !wall, TieBreaking::FewestTurns)
+            .unwrap();
+        assert_eq!(straight_path.len(), default_path.len());
+        assert_eq!(count_turns(&straight_path), 1);
+        assert!(count_turns(&straight_path) < count_turns(&default_path));
+    }
+
+    #[test]
+    fn corridor_region_diameter() {
+        // An L-shaped corridor of 9 cells, and a smaller disconnected region.
+        let mut grid = VecGrid::<bool>::new(Rect::new((6, 6)));
+        let corridor = Rect::with_corners((0, 0), (5, 1))
+            .iter()
+            .chain(Rect::with_corners((4, 1), (5, 5)).iter());
+        for coord in corridor {
+            grid.set(coord, true);
+        }
+        grid.set((0, 5), true);
+        grid.set((1, 5), true);
+        assert_eq!(grid.region_diameter(|&floor| floor), Some(8));
+        assert_eq!(grid.region_diameter(|_| false), None);
+    }
+
+    #[test]
+    fn astar_detours_around_danger() {
+        let grid = VecGrid::<bool>::new(Rect::new((7, 5)));
+        // A threatened straight route along the middle row.
+        let mut danger = VecGrid::new(grid.bounds);
+        danger.fill_rect(Rect::with_corners((1, 1), (6, 4)), 10);
+        let start = Coord::new(0, 2);
+        let goal = Coord::new(6, 2);
+
+        let path = astar_avoiding(&grid, start, goal, |&wall| !wall, &danger).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.len() > grid.astar(start, goal, |&wall| !wall).unwrap().len());
+        assert!(path.iter().all(|&coord| danger.get(coord) == Some(&0)));
+
+        let mismatched = VecGrid::new(Rect::new((3, 3)));
+        assert_eq!(
+            astar_avoiding(&grid, start, goal, |&wall| !wall, &mismatched),
+            None
+        );
+    }
+
+    #[test]
+    fn astar_with_maximum_danger() {
+        let grid = VecGrid::<bool>::new(Rect::new((5, 3)));
+        let mut danger = VecGrid::new(grid.bounds);
+        danger.fill_rect(Rect::with_corners((2, 0), (3, 2)), u32::MAX);
+        let (start, goal) = (Coord::new(0, 0), Coord::new(4, 0));
+        let path = astar_avoiding(&grid, start, goal, |&wall| !wall, &danger).unwrap();
+        assert!(path.iter().all(|&coord| danger.get(coord) == Some(&0)));
+
+        // Crossing the danger as a last resort.
+        danger.fill(u32::MAX);
+        let path = astar_avoiding(&grid, start, goal, |&wall| !wall, &danger).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn all_shortest_paths_across_open_grid() {
+        let grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        let paths = all_shortest_paths(&grid, Coord::new(0, 0), Coord::new(2, 2), |&wall| !wall);
+        // Choosing which 2 of the 4 steps go east.
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5));
+        assert!(paths.iter().all(|path| path[0] == Coord::new(0, 0)));
+        assert!(paths.iter().all(|path| path[4] == Coord::new(2, 2)));
+        let unique_paths = paths.iter().collect::<HashSet<_>>();
+        assert_eq!(unique_paths.len(), 6);
+
+        let single = all_shortest_paths(&grid, Coord::new(1, 1), Coord::new(1, 1), |&wall| !wall);
+        assert_eq!(single, vec![vec![Coord::new(1, 1)]]);
+    }
+
+    #[test]
+    fn all_shortest_paths_unreachable_and_capped() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        grid.fill_rect(Rect::with_corners((1, 0), (2, 3)), true);
+        let paths = all_shortest_paths(&grid, Coord::new(0, 0), Coord::new(2, 2), |&wall| !wall);
+        assert!(paths.is_empty());
+
+        let open = VecGrid::<bool>::new(Rect::new((12, 12)));
+        let paths = all_shortest_paths(&open, Coord::new(0, 0), Coord::new(11, 11), |&wall| !wall);
+        assert_eq!(paths.len(), MAX_SHORTEST_PATHS);
+    }
+
+    #[test]
+    fn waypoint_tour_is_closed() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((6, 6)));
+        grid.fill_rect(Rect::with_corners((2, 1), (3, 5)), true);
+        let waypoints = [Coord::new(0, 0), Coord::new(5, 5), Coord::new(0, 5)];
+        let tour = waypoint_tour(&grid, &waypoints, |&wall| !wall).unwrap();
+        assert_eq!(tour.first(), Some(&Coord::new(0, 0)));
+        assert_eq!(tour.last(), Some(&Coord::new(0, 0)));
+        assert!(waypoints.iter().all(|waypoint| tour.contains(waypoint)));
+        assert!(tour
+            .windows(2)
+            .all(|step| manhattan_distance(step[0], step[1]) == 1));
+        assert!(tour.iter().all(|&coord| grid.get(coord) == Some(&false)));
+        // The nearest waypoint is visited first.
+        let first_visit = tour.iter().position(|&coord| coord == Coord::new(0, 5));
+        let second_visit = tour.iter().position(|&coord| coord == Coord::new(5, 5));
+        assert!(first_visit < second_visit);
+    }
+
+    #[test]
+    fn waypoint_tour_unreachable() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        grid.fill_rect(Rect::with_corners((2, 0), (3, 5)), true);
+        let waypoints = [Coord::new(0, 0), Coord::new(1, 4), Coord::new(4, 4)];
+        assert_eq!(waypoint_tour(&grid, &waypoints, |&wall| !wall), None);
+        assert_eq!(waypoint_tour(&grid, &[], |&wall| !wall), Some(vec![]));
+        assert_eq!(
+            waypoint_tour(&grid, &[Coord::new(1, 1)], |&wall| !wall),
+            Some(vec![Coord::new(1, 1)])
+        );
+    }
+
+    #[test]
+    fn navmesh_l_shape() {
+        let grid = VecGrid::with_generator(Rect::new((7, 6)), |(x, y)| {
+            (x < 2 && y < 5) || ((3..5).contains(&y) && x < 6)
+        });
+        let rects = navmesh(&grid, |&walkable| walkable);
+        assert_eq!(
+            rects,
+            vec![
+                Rect::with_corners((0, 0), (2, 5)),
+                Rect::with_corners((2, 3), (6, 5)),
+            ]
+        );
+        assert!(!any_overlap(&rects));
+        let covered = rects.iter().flat_map(Rect::iter).collect::<HashSet<_>>();
+        let walkable = grid
+            .iter()
+            .filter(|(_coord, &walkable)| walkable)
+            .map(|(coord, _walkable)| coord)
+            .collect::<HashSet<_>>();
+        assert_eq!(covered, walkable);
+    }
+
+    #[test]
+    fn navmesh_without_walkable_cells() {
+        let grid = VecGrid::<bool>::new(Rect::new((4, 4)));
+        assert!(navmesh(&grid, |&walkable| walkable).is_empty());
+    }
+
+    #[test]
+    fn field_of_view_behind_wall() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        grid.set((2, 1), true);
+        let visible = field_of_view(&grid, Coord::new(2, 0), 4, |&wall| wall);
+        assert!(visible.contains(&Coord::new(2, 0)));
+        assert!(visible.contains(&Coord::new(2, 1)));
+        assert!(!visible.contains(&Coord::new(2, 2)));
+        assert!(!visible.contains(&Coord::new(2, 4)));
+        assert!(visible.contains(&Coord::new(0, 2)));
+    }
+
+    #[test]
+    fn open_room_sees_more_than_corridor() {
+        // A room on the left, joined to a narrow corridor along y = 3.
+        let mut grid = VecGrid::with_generator(Rect::new((12, 7)), |(x, y)| x >= 5 && y != 3);
+        grid.fill_rect(Rect::with_corners((5, 0), (12, 3)), true);
+        let visibility = visibility_map(&grid, 4, |&wall| wall);
+        let room_count = *visibility.get((2, 3)).unwrap();
+        let corridor_count = *visibility.get((10, 3)).unwrap();
+        assert!(room_count > corridor_count);
+        assert_eq!(visibility.get((10, 0)), Some(&0));
+    }
+
+    #[test]
+    fn visibility_map_with_tiny_radius() {
+        let grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        let visibility = visibility_map(&grid, -1, |&wall| wall);
+        assert!(visibility.iter().all(|(_coord, &count)| count == 0));
+        let visibility = visibility_map(&grid, 0, |&wall| wall);
+        assert!(visibility.iter().all(|(_coord, &count)| count == 0));
+    }
+
+    #[test]
+    fn single_source_distance_field() {
+        let grid = VecGrid::<bool>::new(Rect::with_corners((-2, -2), (3, 3)));
+        let distances = grid.distance_field([(0, 0)].iter().copied(), |&wall| !wall);
+        assert_eq!(distances.bounds, grid.bounds);
+        assert_eq!(distances.get((0, 0)), Some(&Some(0)));
+        assert_eq!(distances.get((1, 0)), Some(&Some(1)));
+        assert_eq!(distances.get((-2, 2)), Some(&Some(4)));
+    }
+
+    #[test]
+    fn walled_distance_field() {
+        let grid = VecGrid::with_generator(Rect::new((5, 3)), |(x, _y)| x == 2);
+        let sources = [Coord::new(0, 0), Coord::new(2, 1)];
+        let distances = grid.distance_field(sources.iter().copied(), |&wall| !wall);
+        assert_eq!(distances.get((1, 2)), Some(&Some(3)));
+        // The wall itself, and the region on the other side of it.
+        assert_eq!(distances.get((2, 1)), Some(&None));
+        assert!(distances
+            .iter()
+            .filter(|(coord, _distance)| coord.x > 2)
+            .all(|(_coord, distance)| distance.is_none()));
+    }
+}
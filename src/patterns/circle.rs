@@ -23,11 +23,7 @@ impl Circle {
 
         let starting_coord = Coord::new(0, self.radius);
 
-        for coord in self
-            .mirror_quadrants(starting_coord)
-            .iter()
-            .map(|&coord| coord)
-        {
+        for coord in self.mirror_quadrants(starting_coord).iter().copied() {
             coord_queue.push_back(coord);
             seen_coords.insert(coord);
         }
@@ -41,6 +37,41 @@ impl Circle {
         }
     }
 
+    /// Returns every coord of the disk enclosed by the circle (see
+    /// [`FilledCircle::iter`]), along with its Euclidean distance from the
+    /// center.
+    pub fn iter_filled_with_distance(&self) -> impl Iterator<Item = (Coord, f32)> {
+        let center = self.center;
+        FilledCircle::new(center, self.radius)
+            .iter()
+            .map(move |coord| {
+                let offset = coord - center;
+                let distance = ((offset.x * offset.x + offset.y * offset.y) as f32).sqrt();
+                (coord, distance)
+            })
+    }
+
+    /// Returns every coord whose Euclidean distance from the center is within
+    /// `thickness / 2` of the radius, in row-major order. A `thickness` of 1
+    /// gives roughly the same outline as [`iter`](Self::iter), but without
+    /// gaps where the outline is diagonal.
+    pub fn iter_thick(&self, thickness: i32) -> impl Iterator<Item = Coord> {
+        let Circle { center, radius } = *self;
+        let half_thickness = thickness as f32 / 2.0;
+        let (inner, outer) = (
+            radius as f32 - half_thickness,
+            radius as f32 + half_thickness,
+        );
+        let extent = outer.ceil() as i32;
+        (-extent..=extent)
+            .flat_map(move |y| (-extent..=extent).map(move |x| Coord::new(x, y)))
+            .filter(move |offset| {
+                let distance = ((offset.x * offset.x + offset.y * offset.y) as f32).sqrt();
+                inner <= distance && distance <= outer
+            })
+            .map(move |offset| center + offset)
+    }
+
     fn mirror_quadrants(&self, coord: Coord) -> [Coord; 4] {
         [
             self.center + coord,
@@ -64,6 +95,32 @@ impl Circle {
     }
 }
 
+/// A solid disk, as opposed to the outline traced by [`Circle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilledCircle {
+    pub center: Coord,
+    pub radius: i32,
+}
+
+impl FilledCircle {
+    pub fn new<C: Into<Coord>>(center: C, radius: i32) -> Self {
+        Self {
+            center: center.into(),
+            radius,
+        }
+    }
+
+    /// Returns every coord within `radius` (by Euclidean distance) of the
+    /// center, row by row across the circle's bounding box.
+    pub fn iter(&self) -> impl Iterator<Item = Coord> {
+        let FilledCircle { center, radius } = *self;
+        (-radius..=radius)
+            .flat_map(move |y| (-radius..=radius).map(move |x| Coord::new(x, y)))
+            .filter(move |offset| offset.x * offset.x + offset.y * offset.y <= radius * radius)
+            .map(move |offset| center + offset)
+    }
+}
+
 pub struct CircleIter {
     circle: Circle,
     cursor: Coord,
@@ -78,7 +135,7 @@ impl Iterator for CircleIter {
     type Item = Coord;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.coord_queue.len() == 0 && self.cursor.y > self.cursor.x {
+        if self.coord_queue.is_empty() && self.cursor.y > self.cursor.x {
             self.cursor.x += 1;
 
             if self.d < 0 {
@@ -96,10 +153,70 @@ impl Iterator for CircleIter {
             }
         }
 
-        if self.coord_queue.len() > 0 {
+        if !self.coord_queue.is_empty() {
             return self.coord_queue.pop_front();
         }
 
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filled_distances() {
+        let circle = Circle::new((2, -2), 4);
+        let cells = circle.iter_filled_with_distance().collect::<Vec<_>>();
+        assert_eq!(cells.len(), FilledCircle::new((2, -2), 4).iter().count());
+        assert!(cells.contains(&(Coord::new(2, -2), 0.0)));
+        assert!(cells.contains(&(Coord::new(6, -2), 4.0)));
+        let max_distance = cells
+            .iter()
+            .map(|&(_coord, distance)| distance)
+            .fold(0.0, f32::max);
+        assert_eq!(max_distance, 4.0);
+    }
+
+    #[test]
+    fn thick_outline_matches_bresenham() {
+        let circle = Circle::new((1, -1), 8);
+        let outline_count = circle.iter().count() as i32;
+        let thick_count = circle.iter_thick(1).count() as i32;
+        assert!((thick_count - outline_count).abs() <= outline_count / 4);
+        assert!(circle.iter_thick(1).all(|coord| {
+            let offset = coord - circle.center;
+            let distance = ((offset.x * offset.x + offset.y * offset.y) as f32).sqrt();
+            (distance - 8.0).abs() <= 0.5
+        }));
+    }
+
+    #[test]
+    fn thick_ring_widens() {
+        let circle = Circle::new((0, 0), 6);
+        let thin = circle.iter_thick(1).collect::<HashSet<_>>();
+        let thick = circle.iter_thick(3).collect::<Vec<_>>();
+        assert_eq!(thick.len(), thick.iter().collect::<HashSet<_>>().len());
+        assert!(thin.iter().all(|coord| thick.contains(coord)));
+        assert!(thick.contains(&Coord::new(0, 5)));
+        assert!(thick.contains(&Coord::new(7, 0)));
+        assert!(!thick.contains(&Coord::new(4, 0)));
+    }
+
+    #[test]
+    fn filled_circle_counts() {
+        assert_eq!(FilledCircle::new((4, 4), 0).iter().count(), 1);
+        assert_eq!(FilledCircle::new((4, 4), 1).iter().count(), 5);
+        assert_eq!(FilledCircle::new((4, 4), 2).iter().count(), 13);
+    }
+
+    #[test]
+    fn filled_circle_has_no_duplicates() {
+        let coords = FilledCircle::new((-3, 2), 5).iter().collect::<Vec<_>>();
+        let unique_coords = coords.iter().collect::<HashSet<_>>();
+        assert_eq!(coords.len(), unique_coords.len());
+        assert!(coords.contains(&Coord::new(-3, 2)));
+        assert!(coords.contains(&Coord::new(2, 2)));
+    }
+}
@@ -52,7 +52,7 @@ impl Cluster {
     // /// the `internal_border`.
     // ///
     // /// Defined as a non-cluster cell adjacent to at least one cluster cell.
-    pub fn iter_external_border(&self) -> ExternalBorderIter {
+    pub fn iter_external_border(&self) -> ExternalBorderIter<'_> {
         ExternalBorderIter {
             cluster: self,
             coords: self.0.iter(),
@@ -61,6 +61,38 @@ impl Cluster {
         }
     }
 
+    /// The number of cell edges separating the cluster from the exterior.
+    ///
+    /// Each cluster cell contributes one for every orthogonal neighbor outside
+    /// of the cluster, so a thin strip has a larger perimeter than its
+    /// `iter_internal_border` count.
+    pub fn perimeter(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(|&coord| Neighborhood::new(coord).into_iter_ortho())
+            .filter(|neighbor| !self.0.contains(neighbor))
+            .count()
+    }
+
+    /// The directed Hausdorff distance from `self` to `other`: the largest
+    /// Chebyshev distance from any coord of `self` to its nearest coord of
+    /// `other`. Returns `None` if either cluster is empty.
+    ///
+    /// This isn't symmetric, since `other` may have coords far from `self`.
+    pub fn max_distance_to(&self, other: &Cluster) -> Option<i32> {
+        self.0
+            .iter()
+            .map(|coord| {
+                other
+                    .0
+                    .iter()
+                    .map(|&other_coord| coord.chebyshev_distance(other_coord))
+                    .min()
+            })
+            .max()
+            .flatten()
+    }
+
     fn external_neighbors(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
         Neighborhood::new(coord)
             .into_iter()
@@ -85,8 +117,8 @@ impl<'a> Iterator for ExternalBorderIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         // If there are no external border coords to return, loop through the
         // cluster to find external neighbors until new ones are found.
-        if self.coords_to_return.len() == 0 {
-            while let Some(coord) = self.coords.next() {
+        if self.coords_to_return.is_empty() {
+            for coord in self.coords.by_ref() {
                 let external_neighbors = self.cluster.external_neighbors(*coord);
                 for neighbor in external_neighbors {
                     if !self.external_border_coords.contains(&neighbor) {
@@ -97,7 +129,7 @@ impl<'a> Iterator for ExternalBorderIter<'a> {
             }
         }
 
-        if self.coords_to_return.len() > 0 {
+        if !self.coords_to_return.is_empty() {
             return self.coords_to_return.pop_front();
         }
 
@@ -107,11 +139,13 @@ impl<'a> Iterator for ExternalBorderIter<'a> {
 
 #[cfg(test)]
 mod tests {
+    use crate::patterns::Rect;
+
     use super::*;
 
     #[test]
     fn single_coord_cluster_layers() {
-        let cluster = Cluster::new(vec![Coord::ZERO].iter().map(|&c| c));
+        let cluster = Cluster::new([Coord::ZERO].iter().copied());
         assert!(cluster.iter_interior().count() == 0);
         assert!(cluster.iter_internal_border().count() == 1);
         assert!(cluster.iter_external_border().count() == 8);
@@ -140,4 +174,29 @@ mod tests {
         assert!(cluster.iter_internal_border().count() == 8);
         assert!(cluster.iter_external_border().count() == 16);
     }
+
+    #[test]
+    fn square_cluster_perimeter() {
+        let cluster = Cluster::new(Rect::new((3, 3)).iter());
+        assert_eq!(cluster.perimeter(), 12);
+    }
+
+    #[test]
+    fn strip_cluster_perimeter() {
+        let cluster = Cluster::new(Rect::new((4, 1)).iter());
+        assert_eq!(cluster.perimeter(), 10);
+        assert_eq!(cluster.iter_internal_border().count(), 4);
+    }
+
+    #[test]
+    fn offset_cluster_distance() {
+        let cluster = Cluster::new(Rect::new((3, 2)).iter());
+        let offset = Cluster::new(Rect::new((3, 2)).translate((2, -3)).iter());
+        assert_eq!(cluster.max_distance_to(&offset), Some(3));
+        assert_eq!(offset.max_distance_to(&cluster), Some(3));
+        assert_eq!(cluster.max_distance_to(&cluster), Some(0));
+        let empty = Cluster::new(std::iter::empty());
+        assert_eq!(cluster.max_distance_to(&empty), None);
+        assert_eq!(empty.max_distance_to(&cluster), None);
+    }
 }
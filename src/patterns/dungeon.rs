@@ -0,0 +1,228 @@
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{
+    coord::Coord,
+    grid::Grid,
+    patterns::{BspTree, Line, Orientation, Rect},
+    vecgrid::VecGrid,
+};
+
+/// Generates dungeons of rectangular rooms joined by corridors.
+///
+/// The bounds are recursively split with a binary space partition, a randomly
+/// sized room is placed within each leaf, and the rooms of every pair of
+/// sibling partitions are joined by an L-shaped corridor, which guarantees that
+/// all of the floor is connected.
+#[derive(Debug, Clone)]
+pub struct DungeonBuilder<R = ThreadRng> {
+    min_room_size: i32,
+    max_depth: u32,
+    corridor_width: i32,
+    rng: R,
+}
+
+impl DungeonBuilder {
+    pub fn new() -> Self {
+        Self {
+            min_room_size: 4,
+            max_depth: 4,
+            corridor_width: 1,
+            rng: rand::thread_rng(),
+        }
+    }
+}
+
+impl Default for DungeonBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Rng> DungeonBuilder<R> {
+    /// The minimum width and height of a room. Rooms may only be smaller than
+    /// this if the dungeon bounds themselves are too small to fit one.
+    pub fn min_room_size(mut self, min_room_size: i32) -> Self {
+        self.min_room_size = min_room_size.max(1);
+        self
+    }
+
+    /// The maximum number of times the bounds are recursively partitioned,
+    /// which limits the number of rooms to `2^max_depth`.
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The width of the corridors joining rooms, in cells. Widths less than
+    /// one are treated as one.
+    pub fn corridor_width(mut self, corridor_width: i32) -> Self {
+        self.corridor_width = corridor_width.max(1);
+        self
+    }
+
+    /// The source of randomness used for partitioning and room placement.
+    /// Pass a seeded rng for reproducible dungeons.
+    pub fn rng<R2: Rng>(self, rng: R2) -> DungeonBuilder<R2> {
+        DungeonBuilder {
+            min_room_size: self.min_room_size,
+            max_depth: self.max_depth,
+            corridor_width: self.corridor_width,
+            rng,
+        }
+    }
+
+    /// Generates a dungeon within `bounds`, where floor cells are `true` and
+    /// walls are `false`.
+    ///
+    /// Unlike the setters, this borrows the builder rather than consuming it,
+    /// so the same builder can generate several dungeons. Each call advances
+    /// the rng, so successive dungeons differ even when the rng is seeded.
+    pub fn build(&mut self, bounds: Rect) -> VecGrid<bool> {
+        self.generate(bounds).0
+    }
+
+    /// Generates a dungeon, also returning the bounds of each room.
+    fn generate(&mut self, bounds: Rect) -> (VecGrid<bool>, Vec<Rect>) {
+        let mut grid = VecGrid::new(bounds);
+        let mut rooms = vec![];
+        let tree = self.partition(bounds, Orientation::Horizontal, 0);
+        self.carve(&tree, &mut grid, &mut rooms);
+        (grid, rooms)
+    }
+
+    fn partition(&mut self, rect: Rect, orientation: Orientation, depth: u32) -> BspTree {
+        // Leave room for a wall on either side of the smallest room.
+        let min_leaf_size = self.min_room_size + 2;
+        if depth >= self.max_depth {
+            return BspTree::Leaf(rect);
+        }
+        for &orientation in [orientation, orientation.orthogonal()].iter() {
+            let dimension = match orientation {
+                Orientation::Horizontal => rect.width(),
+                Orientation::Vertical => rect.height(),
+            };
+            if dimension < 2 * min_leaf_size {
+                continue;
+            }
+            let partition = self
+                .rng
+                .gen_range(min_leaf_size..=dimension - min_leaf_size);
+            let (left_or_bottom, right_or_top) = match orientation {
+                Orientation::Horizontal => rect.partition_horizontal(partition),
+                Orientation::Vertical => rect.partition_vertical(partition),
+            };
+            let next_orientation = orientation.orthogonal();
+            return BspTree::Node(
+                rect,
+                Box::new(self.partition(left_or_bottom, next_orientation, depth + 1)),
+                Box::new(self.partition(right_or_top, next_orientation, depth + 1)),
+            );
+        }
+        BspTree::Leaf(rect)
+    }
+
+    /// Carves out the rooms and corridors of `tree`, returning a floor coord
+    /// of the carved area.
+    fn carve(
+        &mut self,
+        tree: &BspTree,
+        grid: &mut VecGrid<bool>,
+        rooms: &mut Vec<Rect>,
+    ) -> Option<Coord> {
+        match tree {
+            BspTree::Node(_, left, right) => {
+                let left_coord = self.carve(left, grid, rooms);
+                let right_coord = self.carve(right, grid, rooms);
+                if let (Some(from), Some(to)) = (left_coord, right_coord) {
+                    self.carve_corridor(grid, from, to);
+                }
+                left_coord.or(right_coord)
+            }
+            BspTree::Leaf(leaf) => {
+                let room = self.place_room(*leaf)?;
+                for (_coord, cell) in grid.selection_iter_mut(room.iter()).flatten() {
+                    *cell = true;
+                }
+                rooms.push(room);
+                Some(Coord::new(
+                    room.left + room.width() / 2,
+                    room.top + room.height() / 2,
+                ))
+            }
+        }
+    }
+
+    /// Picks a random room within `leaf`, keeping a one cell margin of wall.
+    fn place_room(&mut self, leaf: Rect) -> Option<Rect> {
+        let inner = Rect {
+            top: leaf.top + 1,
+            bottom: leaf.bottom - 1,
+            left: leaf.left + 1,
+            right: leaf.right - 1,
+        };
+        if inner.width() <= 0 || inner.height() <= 0 {
+            return None;
+        }
+        let width = self
+            .rng
+            .gen_range(self.min_room_size.min(inner.width())..=inner.width());
+        let height = self
+            .rng
+            .gen_range(self.min_room_size.min(inner.height())..=inner.height());
+        let x = self.rng.gen_range(inner.left..=inner.right - width);
+        let y = self.rng.gen_range(inner.top..=inner.bottom - height);
+        Some(Rect::with_corners((x, y), (x + width, y + height)))
+    }
+
+    /// Carves an L-shaped corridor, first horizontally and then vertically.
+    fn carve_corridor(&mut self, grid: &mut VecGrid<bool>, from: Coord, to: Coord) {
+        let corner = Coord::new(to.x, from.y);
+        let path = Line::new(from, corner)
+            .iter()
+            .chain(Line::new(corner, to).iter());
+        // Center the corridor on the path as closely as possible.
+        let half_width = (self.corridor_width - 1) / 2;
+        let brush_size = Coord::new(self.corridor_width, self.corridor_width);
+        for coord in path {
+            let brush_corner = coord - Coord::new(half_width, half_width);
+            let brush = Rect::with_corners(brush_corner, brush_corner + brush_size);
+            for brush_coord in brush.iter() {
+                grid.set(brush_coord, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn seeded_dungeon_is_connected() {
+        let mut builder = DungeonBuilder::new()
+            .min_room_size(5)
+            .max_depth(3)
+            .corridor_width(2)
+            .rng(StdRng::seed_from_u64(42));
+        let (grid, rooms) = builder.generate(Rect::new((48, 48)));
+
+        assert!(rooms.len() > 1);
+        assert!(rooms
+            .iter()
+            .all(|room| room.width() >= 5 && room.height() >= 5));
+
+        let floor_count = grid.iter().filter(|(_coord, &cell)| cell).count();
+        let start = grid.iter().find(|(_coord, &cell)| cell).unwrap().0;
+        assert_eq!(grid.flood_iter(start, |&cell| cell).count(), floor_count);
+    }
+
+    #[test]
+    fn builder_is_reusable() {
+        let mut builder = DungeonBuilder::new().rng(StdRng::seed_from_u64(7));
+        let first = builder.build(Rect::new((32, 32)));
+        let second = builder.build(Rect::new((32, 32)));
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::Coord;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ellipse {
+    pub center: Coord,
+    /// The radius along the x axis.
+    pub rx: i32,
+    /// The radius along the y axis.
+    pub ry: i32,
+}
+
+impl Ellipse {
+    pub fn new<C: Into<Coord>>(center: C, rx: i32, ry: i32) -> Self {
+        Self {
+            center: center.into(),
+            rx,
+            ry,
+        }
+    }
+
+    /// Traces the midpoint ellipse algorithm.
+    pub fn iter(&self) -> EllipseIter {
+        let rx_squared = (self.rx * self.rx) as f32;
+        let ry_squared = (self.ry * self.ry) as f32;
+        EllipseIter {
+            ellipse: *self,
+            cursor: Coord::new(0, self.ry),
+            region: EllipseRegion::Upper,
+            d: ry_squared - rx_squared * self.ry as f32 + rx_squared / 4.0,
+            dx: 0.0,
+            dy: 2.0 * rx_squared * self.ry as f32,
+            coord_queue: VecDeque::new(),
+            seen_coords: HashSet::new(),
+        }
+    }
+
+    fn mirror_quadrants(&self, coord: Coord) -> [Coord; 4] {
+        [
+            self.center + coord,
+            self.center + coord.negate_x(),
+            self.center + coord.negate_y(),
+            self.center + coord.negate(),
+        ]
+    }
+}
+
+/// The part of the first quadrant of the outline being traced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EllipseRegion {
+    /// Near the pole on the y axis, where the outline is flatter than 45
+    /// degrees and each step moves along x.
+    Upper,
+    /// Near the pole on the x axis, where each step moves along y.
+    Lower,
+    /// Finishes the outline along the x axis, which the lower region falls
+    /// short of when `ry` is 0.
+    Axis,
+}
+
+pub struct EllipseIter {
+    ellipse: Ellipse,
+    cursor: Coord,
+    region: EllipseRegion,
+    /// The decision parameter for the current region.
+    d: f32,
+    /// `2 * ry^2 * x`, the change in `d` per step along x.
+    dx: f32,
+    /// `2 * rx^2 * y`, the change in `d` per step along y.
+    dy: f32,
+    /// Coords to be returned on subsequent iterations.
+    coord_queue: VecDeque<Coord>,
+    /// Used to prevent duplicate Coords from being returned.
+    seen_coords: HashSet<Coord>,
+}
+
+impl EllipseIter {
+    /// Returns the next coord of the first quadrant and advances the cursor.
+    fn step(&mut self) -> Option<Coord> {
+        let rx_squared = (self.ellipse.rx * self.ellipse.rx) as f32;
+        let ry_squared = (self.ellipse.ry * self.ellipse.ry) as f32;
+        let coord = self.cursor;
+
+        if self.region == EllipseRegion::Upper {
+            if self.dx < self.dy {
+                self.cursor.x += 1;
+                self.dx += 2.0 * ry_squared;
+                if self.d < 0.0 {
+                    self.d += self.dx + ry_squared;
+                } else {
+                    self.cursor.y -= 1;
+                    self.dy -= 2.0 * rx_squared;
+                    self.d += self.dx - self.dy + ry_squared;
+                }
+                return Some(coord);
+            }
+            let (x, y) = (self.cursor.x as f32 + 0.5, (self.cursor.y - 1) as f32);
+            self.d = ry_squared * x * x + rx_squared * y * y - rx_squared * ry_squared;
+            self.region = EllipseRegion::Lower;
+        }
+
+        if self.region == EllipseRegion::Lower {
+            if self.cursor.y >= 0 {
+                self.cursor.y -= 1;
+                self.dy -= 2.0 * rx_squared;
+                if self.d > 0.0 {
+                    self.d += rx_squared - self.dy;
+                } else {
+                    self.cursor.x += 1;
+                    self.dx += 2.0 * ry_squared;
+                    self.d += self.dx - self.dy + rx_squared;
+                }
+                return Some(coord);
+            }
+            self.cursor.y = 0;
+            self.region = EllipseRegion::Axis;
+        }
+
+        if self.cursor.x <= self.ellipse.rx {
+            let coord = self.cursor;
+            self.cursor.x += 1;
+            return Some(coord);
+        }
+        None
+    }
+}
+
+impl Iterator for EllipseIter {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.coord_queue.is_empty() {
+            let coord = self.step()?;
+            for coord in self.ellipse.mirror_quadrants(coord).iter() {
+                if self.seen_coords.insert(*coord) {
+                    self.coord_queue.push_back(*coord);
+                }
+            }
+        }
+        self.coord_queue.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_ellipse_approximates_circle() {
+        let center = Coord::new(2, -1);
+        let coords = Ellipse::new(center, 5, 5).iter().collect::<Vec<_>>();
+        let unique_coords = coords.iter().collect::<HashSet<_>>();
+        assert_eq!(coords.len(), unique_coords.len());
+        for pole in [(7, -1), (-3, -1), (2, 4), (2, -6)].iter() {
+            assert!(coords.contains(&Coord::from(*pole)));
+        }
+        assert!(coords.iter().all(|&coord| {
+            let offset = coord - center;
+            let distance = ((offset.x * offset.x + offset.y * offset.y) as f32).sqrt();
+            (distance - 5.0).abs() < 1.0
+        }));
+    }
+
+    #[test]
+    fn flat_ellipse_is_horizontal_line() {
+        let coords = Ellipse::new((0, 3), 3, 0).iter().collect::<HashSet<_>>();
+        let line = (-3..=3).map(|x| Coord::new(x, 3)).collect::<HashSet<_>>();
+        assert_eq!(coords, line);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{coord::Coord, grid::Grid, patterns::Rect, vecgrid::VecGrid};
+
+const GLYPH_WIDTH: i32 = 3;
+const GLYPH_HEIGHT: i32 = 5;
+
+/// Renders `text` with a built-in 3x5 bitmap font, with lit pixels as `true`.
+///
+/// Glyphs are separated by a column of unlit pixels, and each line of `text`
+/// by a row of them, with the first line at the top (`y = 0`). Lowercase
+/// letters are drawn as uppercase, and characters without a glyph as `?`.
+pub fn text_to_grid(text: &str) -> VecGrid<bool> {
+    let lines = text.split('\n').collect::<Vec<_>>();
+    let columns = lines
+        .iter()
+        .map(|line| line.chars().count() as i32)
+        .max()
+        .unwrap_or(0);
+    let rows = lines.len() as i32;
+    let mut grid = VecGrid::new(Rect::new((
+        (columns * (GLYPH_WIDTH + 1) - 1).max(0),
+        rows * (GLYPH_HEIGHT + 1) - 1,
+    )));
+
+    for (row, line) in lines.iter().enumerate() {
+        for (column, character) in line.chars().enumerate() {
+            let corner = Coord::new(
+                column as i32 * (GLYPH_WIDTH + 1),
+                row as i32 * (GLYPH_HEIGHT + 1),
+            );
+            for (y, pixels) in glyph(character).iter().enumerate() {
+                for x in 0..GLYPH_WIDTH {
+                    // The leftmost pixel is the highest bit.
+                    if pixels & (1 << (GLYPH_WIDTH - 1 - x)) != 0 {
+                        grid.set(corner + Coord::new(x, y as i32), true);
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+/// The rows of pixels of `character`, from top to bottom.
+#[rustfmt::skip]
+fn glyph(character: char) -> [u8; 5] {
+    match character.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        _ => [0b110, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_art(art: &str) -> VecGrid<bool> {
+        VecGrid::from_ascii(art, '.')
+            .unwrap()
+            .map(|&pixel| pixel == '#')
+    }
+
+    #[test]
+    fn render_single_glyph() {
+        let expected = from_art(".#.\n#.#\n###\n#.#\n#.#");
+        assert_eq!(text_to_grid("A"), expected);
+        assert_eq!(text_to_grid("a"), expected);
+    }
+
+    #[test]
+    fn render_lines() {
+        let grid = text_to_grid("HI\n1");
+        assert_eq!(grid.bounds, Rect::new((7, 11)));
+        let expected = from_art(
+            "#.#.###\n#.#..#.\n###..#.\n#.#..#.\n#.#.###\n.......\n\
+             .#.....\n##.....\n.#.....\n.#.....\n###....",
+        );
+        assert_eq!(grid, expected);
+        assert_eq!(text_to_grid("~"), text_to_grid("?"));
+    }
+}
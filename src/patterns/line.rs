@@ -1,3 +1,5 @@
+use std::{collections::HashSet, iter};
+
 use crate::coord::Coord;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +20,13 @@ impl Line {
         }
     }
 
+    /// Creates a line between floating-point endpoints, rounding each of them
+    /// to the nearest coord.
+    pub fn from_f32(from: (f32, f32), to: (f32, f32)) -> Self {
+        let round = |(x, y): (f32, f32)| Coord::new(x.round() as i32, y.round() as i32);
+        Self::new(round(from), round(to))
+    }
+
     /// Traces Bresenham's line algorithm between `from` and `to`.
     pub fn iter(&self) -> impl Iterator<Item = Coord> {
         let delta = self.to - self.from;
@@ -48,6 +57,97 @@ impl Line {
             is_finished: false,
         }
     }
+
+    /// Traces Xiaolin Wu's anti-aliased line algorithm between `from` and
+    /// `to`, yielding each coord the line touches along with how much of it
+    /// the line covers, from 0.0 to 1.0.
+    ///
+    /// At each step along the major axis, the coverage is split between the
+    /// two coords straddling the line on the minor axis, so it always sums to
+    /// 1.0. Coords with no coverage are skipped, so the endpoints are yielded
+    /// alone with full coverage.
+    pub fn iter_antialiased(&self) -> impl Iterator<Item = (Coord, f32)> {
+        let (from, delta) = (self.from, self.to - self.from);
+        let x_is_major = delta.x.abs() > delta.y.abs();
+        let (major_delta, minor_delta) = if x_is_major {
+            (delta.x, delta.y)
+        } else {
+            (delta.y, delta.x)
+        };
+        let steps = major_delta.abs();
+        let to_coord = move |major: i32, minor: i32| {
+            if x_is_major {
+                Coord::new(from.x + major, minor)
+            } else {
+                Coord::new(minor, from.y + major)
+            }
+        };
+        let minor_start = if x_is_major { from.y } else { from.x };
+
+        (0..=steps).flat_map(move |step| {
+            let major = step * major_delta.signum();
+            let minor = if steps == 0 {
+                minor_start as f32
+            } else {
+                minor_start as f32 + (minor_delta * step) as f32 / steps as f32
+            };
+            let below = minor.floor();
+            let coverage = minor - below;
+            iter::once((to_coord(major, below as i32), 1.0 - coverage))
+                .chain(iter::once((to_coord(major, below as i32 + 1), coverage)))
+                .filter(|&(_coord, coverage)| coverage > 0.0)
+        })
+    }
+
+    /// Returns the coords traced by both `self` and `other`, in the order
+    /// `self` traces them.
+    ///
+    /// Diagonal lines can cross between cells without sharing any, so this may
+    /// be empty even if the lines geometrically intersect.
+    pub fn intersection(&self, other: &Line) -> Vec<Coord> {
+        let other_coords = other.iter().collect::<HashSet<_>>();
+        self.iter()
+            .filter(|coord| other_coords.contains(coord))
+            .collect()
+    }
+
+    /// Traces the same coords as [`iter`](Self::iter), but without the final
+    /// `to` coord, so consecutive segments of a path can be chained without
+    /// covering their shared endpoints twice. The starting `from` coord is
+    /// still included, unless it's also the `to` coord.
+    pub fn iter_exclusive(&self) -> impl Iterator<Item = Coord> {
+        let to = self.to;
+        self.iter().take_while(move |&coord| coord != to)
+    }
+
+    /// Traces the same coords as [`iter`](Self::iter), widening each of them
+    /// by up to `thickness / 2` cells on either side, along the minor axis of
+    /// the line. A `thickness` of 1 or less gives the same coords as `iter`.
+    ///
+    /// Since each coord of `iter` is widened along an axis it never repeats on,
+    /// no coord is yielded twice.
+    pub fn iter_thick(&self, thickness: i32) -> impl Iterator<Item = Coord> {
+        let delta = self.to - self.from;
+        let widen_step = if delta.x.abs() > delta.y.abs() {
+            Coord::new(0, 1)
+        } else {
+            Coord::new(1, 0)
+        };
+        let half_thickness = (thickness / 2).max(0);
+        self.iter().flat_map(move |coord| {
+            (-half_thickness..=half_thickness).map(move |offset| coord + widen_step * offset)
+        })
+    }
+
+    /// Traces the same coords as [`iter`](Self::iter), alternating between
+    /// yielding `on` coords and skipping `off` coords, starting with `on`.
+    pub fn iter_dashed(&self, on: usize, off: usize) -> impl Iterator<Item = Coord> {
+        let period = (on + off).max(1);
+        self.iter()
+            .enumerate()
+            .filter(move |(index, _coord)| index % period < on)
+            .map(|(_index, coord)| coord)
+    }
 }
 
 pub struct LineIter {
@@ -95,3 +195,133 @@ impl Iterator for LineIter {
         Some(return_coord)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashed_horizontal_line() {
+        let coords = Line::new((0, 1), (8, 1))
+            .iter_dashed(2, 2)
+            .collect::<Vec<_>>();
+        let expected = [0, 1, 4, 5, 8]
+            .iter()
+            .map(|&x| Coord::new(x, 1))
+            .collect::<Vec<_>>();
+        assert_eq!(coords, expected);
+        assert_eq!(Line::new((0, 0), (5, 0)).iter_dashed(0, 3).count(), 0);
+    }
+
+    #[test]
+    fn exclusive_segments_share_vertices_once() {
+        let vertices = [Coord::new(0, 0), Coord::new(4, 0), Coord::new(2, 3)];
+        let coords = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .flat_map(|(&from, &to)| Line::new(from, to).iter_exclusive())
+            .collect::<Vec<_>>();
+        for vertex in vertices.iter() {
+            assert_eq!(coords.iter().filter(|&coord| coord == vertex).count(), 1);
+        }
+        assert_eq!(coords.len(), coords.iter().collect::<HashSet<_>>().len());
+
+        let line = Line::new((1, 1), (5, 3));
+        assert_eq!(line.iter_exclusive().count(), line.iter().count() - 1);
+        assert!(!line.iter_exclusive().any(|coord| coord == Coord::new(5, 3)));
+        assert_eq!(Line::new((2, 2), (2, 2)).iter_exclusive().count(), 0);
+    }
+
+    #[test]
+    fn antialiased_shallow_line() {
+        let cells = Line::new((0, 0), (10, -3))
+            .iter_antialiased()
+            .collect::<Vec<_>>();
+        assert!(cells.contains(&(Coord::new(0, 0), 1.0)));
+        assert!(cells.contains(&(Coord::new(10, -3), 1.0)));
+        for x in 0..=10 {
+            let coverage = cells
+                .iter()
+                .filter(|(coord, _coverage)| coord.x == x)
+                .map(|&(_coord, coverage)| coverage)
+                .sum::<f32>();
+            assert!((coverage - 1.0).abs() < 1e-5);
+        }
+        assert!(cells
+            .iter()
+            .any(|&(_coord, coverage)| 0.0 < coverage && coverage < 1.0));
+        assert!(cells
+            .iter()
+            .all(|&(_coord, coverage)| 0.0 < coverage && coverage <= 1.0));
+    }
+
+    #[test]
+    fn antialiased_axis_line_has_full_coverage() {
+        let line = Line::new((3, 1), (3, 6));
+        let cells = line.iter_antialiased().collect::<Vec<_>>();
+        let expected = line.iter().map(|coord| (coord, 1.0)).collect::<Vec<_>>();
+        assert_eq!(cells, expected);
+        assert_eq!(
+            Line::new((2, 2), (2, 2))
+                .iter_antialiased()
+                .collect::<Vec<_>>(),
+            vec![(Coord::new(2, 2), 1.0)]
+        );
+    }
+
+    #[test]
+    fn crossing_lines_intersection() {
+        let a = Line::new((0, 0), (4, 4));
+        let b = Line::new((0, 4), (4, 0));
+        assert_eq!(a.intersection(&b), vec![Coord::new(2, 2)]);
+        let corridor = Line::new((2, -3), (2, 8));
+        assert_eq!(
+            Line::new((-1, 5), (6, 5)).intersection(&corridor),
+            vec![Coord::new(2, 5)]
+        );
+    }
+
+    #[test]
+    fn parallel_lines_intersection() {
+        let a = Line::new((0, 0), (5, 0));
+        let b = Line::new((0, 2), (5, 2));
+        assert!(a.intersection(&b).is_empty());
+        assert_eq!(a.intersection(&a).len(), 6);
+    }
+
+    #[test]
+    fn thin_line_matches_iter() {
+        let line = Line::new((-2, 1), (5, -3));
+        assert_eq!(
+            line.iter_thick(1).collect::<Vec<_>>(),
+            line.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn thick_horizontal_line_spans_three_rows() {
+        let coords = Line::new((0, 4), (6, 4)).iter_thick(3).collect::<Vec<_>>();
+        assert_eq!(coords.len(), 21);
+        for y in 3..=5 {
+            assert!((0..=6).all(|x| coords.contains(&Coord::new(x, y))));
+        }
+    }
+
+    #[test]
+    fn thick_steep_line_widens_horizontally() {
+        let line = Line::new((0, 0), (1, 6));
+        let coords = line.iter_thick(5).collect::<HashSet<_>>();
+        assert_eq!(coords.len(), 35);
+        for coord in line.iter() {
+            assert!((-2..=2).all(|x| coords.contains(&(coord + Coord::new(x, 0)))));
+        }
+    }
+
+    #[test]
+    fn line_from_f32_rounds_endpoints() {
+        assert_eq!(
+            Line::from_f32((1.4, 2.6), (-0.6, 3.2)),
+            Line::new((1, 3), (-1, 3))
+        );
+    }
+}
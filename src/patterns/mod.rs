@@ -8,12 +8,26 @@
 
 mod circle;
 mod cluster;
+mod dungeon;
+mod ellipse;
+mod font;
 mod line;
 mod neighborhood;
+mod poisson;
+mod polygon;
 mod rect;
+mod transform;
+mod wfc;
 
-pub use circle::{Circle, CircleIter};
+pub use circle::{Circle, CircleIter, FilledCircle};
 pub use cluster::{Cluster, ExternalBorderIter};
+pub use dungeon::DungeonBuilder;
+pub use ellipse::{Ellipse, EllipseIter};
+pub use font::text_to_grid;
 pub use line::{Line, LineIter};
-pub use neighborhood::Neighborhood;
-pub use rect::{BspTree, Orientation, Rect, RectIter};
+pub use neighborhood::{manhattan_ring, Connectivity, Neighborhood};
+pub use poisson::poisson_disc;
+pub use polygon::Polygon;
+pub use rect::{any_overlap, first_overlap, place_rooms, BspTree, Orientation, Rect, RectIter};
+pub use transform::{reflect, rotate, transform, translate};
+pub use wfc::wfc;
@@ -20,6 +20,26 @@ const DIAG_NEIGHBOR_OFFSETS: [Coord; 4] = [
     Coord::NORTH_WEST,
 ];
 
+/// Which cells count as adjacent when walking between cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Only orthogonally adjacent (Von Neumann) cells, so diagonally touching
+    /// cells are not connected.
+    Orthogonal,
+    /// Both orthogonally and diagonally adjacent (Moore) cells.
+    Moore,
+}
+
+impl Connectivity {
+    /// The offsets from a cell to each of its adjacent cells.
+    pub fn offsets(&self) -> &'static [Coord] {
+        match self {
+            Connectivity::Orthogonal => &ORTHO_NEIGHBOR_OFFSETS,
+            Connectivity::Moore => &NEIGHBOR_OFFSETS,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Neighborhood(Coord);
 
@@ -34,6 +54,7 @@ impl Neighborhood {
     }
 
     /// Returns the orthogonal and diagonal (Moore) neighborhood of `coord`.
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> impl Iterator<Item = Coord> {
         NEIGHBOR_OFFSETS.iter().map(move |&offset| self.0 + offset)
     }
@@ -65,4 +86,94 @@ impl Neighborhood {
             .iter()
             .map(move |&offset| self.0 + offset)
     }
+
+    /// Returns every coord within Chebyshev distance `radius` of `coord`,
+    /// excluding `coord` itself, in row-major order.
+    pub fn iter_moore(&self, radius: i32) -> impl Iterator<Item = Coord> {
+        let center = self.0;
+        (-radius..=radius)
+            .flat_map(move |y| (-radius..=radius).map(move |x| Coord::new(x, y)))
+            .filter(|&offset| offset != Coord::ZERO)
+            .map(move |offset| center + offset)
+    }
+
+    /// Returns every coord within Manhattan distance `radius` of `coord`,
+    /// excluding `coord` itself, from the nearest ring outwards.
+    pub fn iter_von_neumann(&self, radius: i32) -> impl Iterator<Item = Coord> {
+        let center = self.0;
+        (1..=radius).flat_map(move |distance| manhattan_ring(center, distance))
+    }
+}
+
+/// Returns the coords at exactly `distance` Manhattan distance from `center`,
+/// forming the outline of a diamond. A `distance` of 0 yields only `center`.
+pub fn manhattan_ring(center: Coord, distance: i32) -> impl Iterator<Item = Coord> {
+    let count = match distance {
+        0 => 1,
+        d if d < 0 => 0,
+        d => 4 * d,
+    };
+    (0..count).map(move |step| {
+        if distance == 0 {
+            return center;
+        }
+        let i = step % distance;
+        let j = distance - i;
+        // Walk each side of the diamond counter-clockwise, starting from east.
+        let offset = match step / distance {
+            0 => Coord::new(j, i),
+            1 => Coord::new(-i, j),
+            2 => Coord::new(-j, -i),
+            _ => Coord::new(i, -j),
+        };
+        center + offset
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn moore_radius_counts() {
+        let neighborhood = Neighborhood::new((2, -3));
+        assert_eq!(neighborhood.iter_moore(0).count(), 0);
+        assert_eq!(
+            neighborhood.iter_moore(1).collect::<HashSet<_>>(),
+            neighborhood.iter().collect::<HashSet<_>>()
+        );
+        assert_eq!(neighborhood.iter_moore(2).count(), 24);
+    }
+
+    #[test]
+    fn von_neumann_radius_counts() {
+        let neighborhood = Neighborhood::new((2, -3));
+        assert_eq!(neighborhood.iter_von_neumann(0).count(), 0);
+        assert_eq!(
+            neighborhood.iter_von_neumann(1).collect::<HashSet<_>>(),
+            neighborhood.iter_ortho().collect::<HashSet<_>>()
+        );
+        let coords = neighborhood.iter_von_neumann(2).collect::<HashSet<_>>();
+        assert_eq!(coords.len(), 12);
+        assert!(!coords.contains(&Coord::new(2, -3)));
+    }
+
+    #[test]
+    fn manhattan_ring_zero_distance() {
+        let coords = manhattan_ring(Coord::new(3, 3), 0).collect::<Vec<_>>();
+        assert_eq!(coords, vec![Coord::new(3, 3)]);
+    }
+
+    #[test]
+    fn manhattan_ring_diamond() {
+        let center = Coord::new(1, -1);
+        let coords = manhattan_ring(center, 2).collect::<HashSet<_>>();
+        assert_eq!(coords.len(), 8);
+        assert!(coords.iter().all(|&coord| {
+            let delta = coord - center;
+            delta.x.abs() + delta.y.abs() == 2
+        }));
+    }
 }
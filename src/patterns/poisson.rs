@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    f32::consts::{SQRT_2, TAU},
+    iter,
+};
+
+use rand::Rng;
+
+use crate::{
+    coord::Coord,
+    patterns::{Neighborhood, Rect},
+};
+
+/// Scatters coords across `bounds` using Bridson's algorithm, so that no two
+/// are closer than `min_distance` (by Euclidean distance) but the gaps between
+/// them are filled, like trees in a forest.
+///
+/// A `min_distance` below 1 is treated as 1, so coords are never repeated. The
+/// coords are returned in the order they were placed.
+pub fn poisson_disc(bounds: Rect, min_distance: i32, rng: &mut impl Rng) -> Vec<Coord> {
+    const CANDIDATES_PER_POINT: usize = 30;
+
+    if bounds.area() <= 0 {
+        return vec![];
+    }
+    let min_distance = min_distance.max(1);
+    // Small enough that each background cell holds at most one point.
+    let cell_size = ((min_distance as f32 / SQRT_2) as i32).max(1);
+    let search_radius = (min_distance + cell_size - 1) / cell_size;
+    let cell_of = |coord: Coord| {
+        let local = coord - bounds.offset();
+        Coord::new(local.x / cell_size, local.y / cell_size)
+    };
+    let fits = |cells: &HashMap<Coord, Coord>, candidate: Coord| {
+        bounds.contains(candidate)
+            && Neighborhood::new(cell_of(candidate))
+                .iter_moore(search_radius)
+                .chain(iter::once(cell_of(candidate)))
+                .filter_map(|cell| cells.get(&cell))
+                .all(|&point| {
+                    let offset = point - candidate;
+                    offset.x * offset.x + offset.y * offset.y >= min_distance * min_distance
+                })
+    };
+
+    let first = Coord::new(
+        rng.gen_range(bounds.x_range()),
+        rng.gen_range(bounds.y_range()),
+    );
+    let mut cells = HashMap::new();
+    cells.insert(cell_of(first), first);
+    let mut points = vec![first];
+    let mut active = vec![first];
+    while !active.is_empty() {
+        let index = rng.gen_range(0..active.len());
+        let point = active[index];
+        let mut placed = None;
+        for _ in 0..CANDIDATES_PER_POINT {
+            // Candidates come from the annulus between one and two times the
+            // minimum distance.
+            let angle = rng.gen_range(0.0..TAU);
+            let distance = rng.gen_range(min_distance as f32..2.0 * min_distance as f32);
+            let candidate = point
+                + Coord::new(
+                    (distance * angle.cos()).round() as i32,
+                    (distance * angle.sin()).round() as i32,
+                );
+            if fits(&cells, candidate) {
+                placed = Some(candidate);
+                break;
+            }
+        }
+        match placed {
+            Some(candidate) => {
+                cells.insert(cell_of(candidate), candidate);
+                points.push(candidate);
+                active.push(candidate);
+            }
+            None => {
+                active.swap_remove(index);
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn poisson_disc_respects_min_distance() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let bounds = Rect::with_corners((-5, 3), (25, 18));
+        let points = poisson_disc(bounds, 3, &mut rng);
+        assert!(points.len() > 20);
+        assert!(points.iter().all(|&point| bounds.contains(point)));
+        for (i, &a) in points.iter().enumerate() {
+            for &b in &points[i + 1..] {
+                let offset = a - b;
+                assert!(offset.x * offset.x + offset.y * offset.y >= 9);
+            }
+        }
+    }
+
+    #[test]
+    fn poisson_disc_small_bounds() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(poisson_disc(Rect::new((2, 2)), 5, &mut rng).len(), 1);
+        let dense = poisson_disc(Rect::new((3, 2)), 0, &mut rng);
+        assert!(dense.len() <= 6);
+        assert_eq!(dense.iter().collect::<HashSet<_>>().len(), dense.len());
+        assert!(poisson_disc(Rect::new((0, 4)), 2, &mut rng).is_empty());
+    }
+}
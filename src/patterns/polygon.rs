@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use crate::{coord::Coord, patterns::Line};
+
+/// A closed shape with straight [`Line`] edges between consecutive vertices,
+/// and from the last vertex back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<Coord>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Coord>) -> Self {
+        Self { vertices }
+    }
+
+    /// Traces each edge of the polygon in order, yielding every coord once,
+    /// even where edges meet or cross.
+    pub fn iter_outline(&self) -> impl Iterator<Item = Coord> + '_ {
+        let mut seen_coords = HashSet::new();
+        let closing_vertices = self.vertices.iter().cycle().skip(1);
+        // A single vertex has no edges to trace, so yield it on its own.
+        let lone_vertex = match self.vertices.as_slice() {
+            [vertex] => Some(*vertex),
+            _ => None,
+        };
+        self.vertices
+            .iter()
+            .zip(closing_vertices)
+            .flat_map(|(&from, &to)| Line::new(from, to).iter_exclusive())
+            .chain(lone_vertex)
+            .filter(move |&coord| seen_coords.insert(coord))
+    }
+
+    /// Returns every coord on or within the outline, in row-major order.
+    ///
+    /// The interior is filled with the even-odd rule, scanning across each row
+    /// through the center of its cells. With fewer than 3 vertices there's no
+    /// interior, so only the outline is returned.
+    pub fn iter_filled(&self) -> impl Iterator<Item = Coord> {
+        let mut coords = self.iter_outline().collect::<HashSet<_>>();
+        if self.vertices.len() >= 3 {
+            let edges = self
+                .vertices
+                .iter()
+                .zip(self.vertices.iter().cycle().skip(1))
+                .collect::<Vec<_>>();
+            let top = self.vertices.iter().map(|vertex| vertex.y).min().unwrap();
+            let bottom = self.vertices.iter().map(|vertex| vertex.y).max().unwrap();
+            for y in top..=bottom {
+                // Each edge crosses rows in the half-open range of its
+                // endpoints, so shared vertices aren't counted twice.
+                let mut crossings = edges
+                    .iter()
+                    .filter(|(a, b)| (a.y <= y) != (b.y <= y))
+                    .map(|(a, b)| {
+                        a.x as f32 + (y - a.y) as f32 * (b.x - a.x) as f32 / (b.y - a.y) as f32
+                    })
+                    .collect::<Vec<_>>();
+                crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                for span in crossings.chunks_exact(2) {
+                    let (left, right) = (span[0].ceil() as i32, span[1].floor() as i32);
+                    coords.extend((left..=right).map(|x| Coord::new(x, y)));
+                }
+            }
+        }
+        let mut coords = coords.into_iter().collect::<Vec<_>>();
+        coords.sort_by_key(|coord| (coord.y, coord.x));
+        coords.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::patterns::Rect;
+
+    use super::*;
+
+    #[test]
+    fn triangle_outline() {
+        let triangle = Polygon::new(vec![Coord::new(0, 0), Coord::new(4, 0), Coord::new(0, 4)]);
+        let outline = triangle.iter_outline().collect::<Vec<_>>();
+        assert_eq!(outline.len(), 12);
+        assert_eq!(outline.iter().collect::<HashSet<_>>().len(), 12);
+        assert!(triangle
+            .vertices
+            .iter()
+            .all(|vertex| outline.contains(vertex)));
+    }
+
+    #[test]
+    fn filled_square_matches_rect() {
+        let square = Polygon::new(vec![
+            Coord::new(-1, 2),
+            Coord::new(3, 2),
+            Coord::new(3, 6),
+            Coord::new(-1, 6),
+        ]);
+        let filled = square.iter_filled().collect::<Vec<_>>();
+        let rect = Rect::with_corners((-1, 2), (4, 7))
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(filled, rect);
+    }
+
+    #[test]
+    fn filled_triangle_contains_interior() {
+        let triangle = Polygon::new(vec![Coord::new(0, 0), Coord::new(8, 0), Coord::new(0, 8)]);
+        let filled = triangle.iter_filled().collect::<HashSet<_>>();
+        assert!(filled.contains(&Coord::new(2, 2)));
+        assert!(filled.contains(&Coord::new(4, 4)));
+        assert!(!filled.contains(&Coord::new(5, 5)));
+        // The cells on or under the diagonal x + y = 8.
+        assert_eq!(filled.len(), 45);
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        assert_eq!(Polygon::new(vec![]).iter_filled().count(), 0);
+        let point = Polygon::new(vec![Coord::new(2, 3)]);
+        assert_eq!(
+            point.iter_filled().collect::<Vec<_>>(),
+            vec![Coord::new(2, 3)]
+        );
+        let segment = Polygon::new(vec![Coord::new(0, 0), Coord::new(3, 0)]);
+        assert_eq!(segment.iter_outline().count(), 4);
+        assert_eq!(segment.iter_filled().count(), 4);
+    }
+}
@@ -1,8 +1,11 @@
 use std::ops::Range;
 
+use rand::Rng;
+
 use crate::coord::Coord;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub top: i32,
     pub bottom: i32,
@@ -120,6 +123,49 @@ impl Rect {
         }
     }
 
+    /// Returns the coord in the middle of the rect. Where a dimension is even
+    /// the middle falls between two cells, and this picks the one with the
+    /// higher coordinate, so a 4x4 rect at the origin has its center at
+    /// (2, 2).
+    pub fn center(&self) -> Coord {
+        Coord::new(self.left + self.width() / 2, self.top + self.height() / 2)
+    }
+
+    /// Scales the width and height of the rect by `factor`, rounding to the
+    /// nearest cell but never below 1x1, keeping the same
+    /// [`center`](Self::center).
+    pub fn scale(&self, factor: f32) -> Self {
+        let scale_dimension = |dimension: i32| ((dimension as f32 * factor).round() as i32).max(1);
+        let dimensions = Coord::new(
+            scale_dimension(self.width()),
+            scale_dimension(self.height()),
+        );
+        let corner = self.center() - Coord::new(dimensions.x / 2, dimensions.y / 2);
+        Rect::with_corners(corner, corner + dimensions)
+    }
+
+    /// Returns the area covered by both rects, or `None` if they don't share
+    /// any coords.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let intersection = Rect {
+            top: self.top.max(other.top),
+            bottom: self.bottom.min(other.bottom),
+            left: self.left.max(other.left),
+            right: self.right.min(other.right),
+        };
+        if intersection.width() > 0 && intersection.height() > 0 {
+            Some(intersection)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the rects share any coords. Rects that merely touch along an
+    /// edge don't overlap.
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
     pub fn contains<C: Into<Coord>>(&self, coord: C) -> bool {
         let coord = coord.into();
         coord.x >= self.left && coord.x < self.right && coord.y >= self.top && coord.y < self.bottom
@@ -142,6 +188,82 @@ impl Rect {
             is_finished: false,
         }
     }
+
+    /// Returns each coord on the outermost ring of the rect exactly once, in
+    /// row-major order, without visiting the interior.
+    pub fn border_iter(&self) -> impl Iterator<Item = Coord> {
+        let Rect {
+            top,
+            bottom,
+            left,
+            right,
+        } = *self;
+        (top..bottom).flat_map(move |y| {
+            // Skip straight from the left side to the right side on inner rows.
+            let x_step = if y == top || y == bottom - 1 {
+                1
+            } else {
+                (right - left - 1).max(1)
+            };
+            (left..right)
+                .step_by(x_step as usize)
+                .map(move |x| Coord::new(x, y))
+        })
+    }
+}
+
+/// Whether any two of `rects` overlap, such as when validating room placement.
+pub fn any_overlap(rects: &[Rect]) -> bool {
+    first_overlap(rects).is_some()
+}
+
+/// Returns the indices of the first pair of `rects` that overlap, ordered by
+/// the first index and then the second.
+pub fn first_overlap(rects: &[Rect]) -> Option<(usize, usize)> {
+    rects.iter().enumerate().find_map(|(i, a)| {
+        rects[i + 1..]
+            .iter()
+            .position(|b| a.overlaps(b))
+            .map(|offset| (i, i + 1 + offset))
+    })
+}
+
+/// Attempts to place up to `count` randomly sized rooms within `bounds`, each
+/// between `min` and `max` (inclusive) in size, without overlapping any
+/// previously placed room.
+///
+/// A room is given up on after several failed attempts to place it, so fewer
+/// than `count` rooms may be returned when `bounds` is crowded.
+pub fn place_rooms(
+    bounds: Rect,
+    count: usize,
+    min: Coord,
+    max: Coord,
+    rng: &mut impl Rng,
+) -> Vec<Rect> {
+    const ATTEMPTS_PER_ROOM: usize = 30;
+
+    let min = Coord::new(min.x.max(1), min.y.max(1));
+    let max = Coord::new(max.x.min(bounds.width()), max.y.min(bounds.height()));
+    if min.x > max.x || min.y > max.y {
+        return vec![];
+    }
+    let mut rooms: Vec<Rect> = vec![];
+    for _ in 0..count {
+        for _ in 0..ATTEMPTS_PER_ROOM {
+            let size = Coord::new(rng.gen_range(min.x..=max.x), rng.gen_range(min.y..=max.y));
+            let corner = Coord::new(
+                rng.gen_range(bounds.left..=bounds.right - size.x),
+                rng.gen_range(bounds.top..=bounds.bottom - size.y),
+            );
+            let room = Rect::with_corners(corner, corner + size);
+            if !rooms.iter().any(|other| other.overlaps(&room)) {
+                rooms.push(room);
+                break;
+            }
+        }
+    }
+    rooms
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -176,6 +298,32 @@ impl BspTree {
             BspTree::Leaf(rect) => vec![*rect],
         }
     }
+
+    /// Returns a copy of the tree with undersized leaves merged away.
+    ///
+    /// The tree is pruned bottom-up: any node whose children are both leaves,
+    /// where at least one of them has an area below `min_area`, is collapsed
+    /// into a single leaf covering the node's entire rect. Since collapsing can
+    /// turn a node into a leaf, merges may cascade up towards the root. A small
+    /// leaf whose sibling is still a subtree is left untouched, as merging it
+    /// would discard the sibling's partitions.
+    pub fn merge_small(&self, min_area: i32) -> BspTree {
+        match self {
+            BspTree::Node(rect, left, right) => {
+                let left = left.merge_small(min_area);
+                let right = right.merge_small(min_area);
+                match (&left, &right) {
+                    (BspTree::Leaf(left_rect), BspTree::Leaf(right_rect))
+                        if left_rect.area() < min_area || right_rect.area() < min_area =>
+                    {
+                        BspTree::Leaf(*rect)
+                    }
+                    _ => BspTree::Node(*rect, Box::new(left), Box::new(right)),
+                }
+            }
+            BspTree::Leaf(rect) => BspTree::Leaf(*rect),
+        }
+    }
 }
 
 /// Iterates row by row from the bottom-left corner to the top-right corner.
@@ -212,8 +360,107 @@ impl Iterator for RectIter {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
     use super::*;
 
+    #[test]
+    fn even_and_odd_centers() {
+        assert_eq!(Rect::new((4, 4)).center(), Coord::new(2, 2));
+        assert_eq!(
+            Rect::with_corners((1, -2), (6, 1)).center(),
+            Coord::new(3, -1)
+        );
+        assert_eq!(
+            Rect::with_corners((-3, 0), (3, 1)).center(),
+            Coord::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn scale_about_center() {
+        let rect = Rect::with_corners((2, 1), (10, 7));
+        let half = rect.scale(0.5);
+        assert_eq!(half, Rect::with_corners((4, 3), (8, 6)));
+        assert_eq!(half.area(), rect.area() / 4);
+        assert_eq!(half.center(), rect.center());
+        assert_eq!(rect.scale(2.0).center(), rect.center());
+        assert_eq!(rect.scale(2.0).dimensions(), Coord::new(16, 12));
+        assert_eq!(rect.scale(0.0), Rect::with_corners((6, 4), (7, 5)));
+    }
+
+    #[test]
+    fn rect_intersection() {
+        let a = Rect::with_corners((0, 0), (4, 3));
+        let b = Rect::with_corners((2, -1), (6, 2));
+        assert_eq!(a.intersection(&b), Some(Rect::with_corners((2, 0), (4, 2))));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+        // Touching edges share no coords.
+        let c = Rect::with_corners((4, 0), (5, 3));
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn room_overlaps() {
+        let rooms = [
+            Rect::with_corners((0, 0), (3, 3)),
+            Rect::with_corners((3, 0), (6, 3)),
+            Rect::with_corners((0, 4), (6, 6)),
+        ];
+        assert!(!any_overlap(&rooms));
+        assert_eq!(first_overlap(&rooms), None);
+
+        let mut rooms = rooms.to_vec();
+        rooms.push(Rect::with_corners((5, 5), (7, 7)));
+        assert!(any_overlap(&rooms));
+        assert_eq!(first_overlap(&rooms), Some((2, 3)));
+    }
+
+    #[test]
+    fn placed_rooms_fit_without_overlap() {
+        let bounds = Rect::with_corners((-10, -10), (30, 20));
+        let mut rng = StdRng::seed_from_u64(5);
+        let rooms = place_rooms(bounds, 12, Coord::new(3, 3), Coord::new(8, 6), &mut rng);
+        assert!(rooms.len() > 1);
+        assert!(!any_overlap(&rooms));
+        for room in rooms.iter() {
+            assert!(room.left >= bounds.left && room.right <= bounds.right);
+            assert!(room.top >= bounds.top && room.bottom <= bounds.bottom);
+            assert!((3..=8).contains(&room.width()));
+            assert!((3..=6).contains(&room.height()));
+        }
+    }
+
+    #[test]
+    fn border_coords() {
+        let border = Rect::new((4, 4)).border_iter().collect::<Vec<_>>();
+        assert_eq!(border.len(), 12);
+        assert_eq!(border.iter().collect::<HashSet<_>>().len(), 12);
+        assert!(!border.contains(&Coord::new(1, 1)));
+        assert!(!border.contains(&Coord::new(2, 2)));
+        assert_eq!(
+            Rect::new((1, 1)).border_iter().collect::<Vec<_>>(),
+            vec![Coord::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn thin_rect_is_all_border() {
+        let column = Rect::with_corners((2, -1), (3, 4));
+        assert_eq!(
+            column.border_iter().collect::<Vec<_>>(),
+            column.iter().collect::<Vec<_>>()
+        );
+        let row = Rect::new((5, 1));
+        assert_eq!(
+            row.border_iter().collect::<Vec<_>>(),
+            row.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(Rect::new((0, 3)).border_iter().count(), 0);
+    }
+
     #[test]
     fn dimensions() {
         let rect = Rect::new((3, 4));
@@ -296,4 +543,23 @@ mod tests {
         assert_eq!(bsp_leaves.len(), 16);
         assert!(bsp_leaves.iter().all(|rect| rect.area() == 16));
     }
+
+    #[test]
+    fn merge_small_bsp_leaves() {
+        let rect = Rect::new((16, 16));
+        // Split into two halves, then cut a thin sliver off of each half.
+        let tree = rect.bsp(
+            Orientation::Horizontal,
+            &|rect, orientation| match orientation {
+                Orientation::Horizontal if rect.width() == 16 => Some((8, Orientation::Vertical)),
+                Orientation::Vertical if rect.height() == 16 => Some((2, Orientation::Horizontal)),
+                _ => None,
+            },
+        );
+        assert_eq!(tree.leaves().len(), 4);
+
+        let merged_leaves = tree.merge_small(20).leaves();
+        assert_eq!(merged_leaves.len(), 2);
+        assert!(merged_leaves.iter().all(|rect| rect.area() >= 20));
+    }
 }
@@ -0,0 +1,66 @@
+use crate::{coord::Coord, patterns::Orientation};
+
+/// Lazily applies `f` to each coord of a pattern, such as to move or rotate it
+/// before passing it into
+/// [`Grid::selection_iter`](crate::grid::Grid::selection_iter).
+///
+/// [`translate`], [`rotate`], and [`reflect`] build common transformations.
+pub fn transform(
+    coords: impl Iterator<Item = Coord>,
+    f: impl Fn(Coord) -> Coord,
+) -> impl Iterator<Item = Coord> {
+    coords.map(f)
+}
+
+/// Moves each coord by `offset`.
+pub fn translate<C: Into<Coord>>(offset: C) -> impl Fn(Coord) -> Coord {
+    let offset = offset.into();
+    move |coord| coord + offset
+}
+
+/// Rotates each coord about the origin by `quarter_turns` clockwise quarter
+/// turns (see [`Coord::rotate_cw`]). Negative values turn counterclockwise.
+pub fn rotate(quarter_turns: i32) -> impl Fn(Coord) -> Coord {
+    move |coord| match quarter_turns.rem_euclid(4) {
+        0 => coord,
+        1 => coord.rotate_cw(),
+        2 => coord.negate(),
+        _ => coord.rotate_ccw(),
+    }
+}
+
+/// Reflects each coord across the axis through the origin with the given
+/// `orientation`, so [`Orientation::Horizontal`] mirrors across the x-axis.
+pub fn reflect(orientation: Orientation) -> impl Fn(Coord) -> Coord {
+    move |coord| match orientation {
+        Orientation::Horizontal => coord.negate_y(),
+        Orientation::Vertical => coord.negate_x(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::patterns::Circle;
+
+    use super::*;
+
+    #[test]
+    fn translated_circle() {
+        let circle = Circle::new((0, 0), 3);
+        let translated = transform(circle.iter(), translate((5, -2))).collect::<HashSet<_>>();
+        let moved_circle = Circle::new((5, -2), 3).iter().collect::<HashSet<_>>();
+        assert_eq!(translated, moved_circle);
+    }
+
+    #[test]
+    fn rotate_and_reflect() {
+        let coords = [Coord::new(2, 1)];
+        let rotated = transform(coords.iter().copied(), rotate(-1)).collect::<Vec<_>>();
+        assert_eq!(rotated, vec![Coord::new(-1, 2)]);
+        let reflected =
+            transform(coords.iter().copied(), reflect(Orientation::Horizontal)).collect::<Vec<_>>();
+        assert_eq!(reflected, vec![Coord::new(2, -1)]);
+    }
+}
@@ -0,0 +1,149 @@
+use rand::Rng;
+
+use crate::{
+    coord::Coord,
+    grid::Grid,
+    patterns::{Connectivity, Rect},
+    vecgrid::VecGrid,
+};
+
+/// Fills `bounds` with `tiles` using the wave function collapse algorithm, so
+/// that every pair of orthogonally adjacent cells satisfies `adjacency`.
+///
+/// `adjacency(a, b, offset)` returns whether tile `b` may be placed at `offset`
+/// from tile `a`. Starting with every tile possible in every cell, the cell
+/// with the fewest remaining possibilities is repeatedly collapsed to one of
+/// them at random, and the tiles that can no longer fit its neighbors are
+/// ruled out, spreading outwards. There's no backtracking, so this returns
+/// `None` as soon as some cell has no possible tiles left.
+pub fn wfc<T: Clone>(
+    bounds: Rect,
+    tiles: &[T],
+    adjacency: impl Fn(&T, &T, Coord) -> bool,
+    rng: &mut impl Rng,
+) -> Option<VecGrid<T>> {
+    // `allowed[direction][a][b]` caches `adjacency(a, b, offset)` for each of
+    // the orthogonal offsets.
+    let allowed = Connectivity::Orthogonal
+        .offsets()
+        .iter()
+        .map(|&offset| {
+            tiles
+                .iter()
+                .map(|a| tiles.iter().map(|b| adjacency(a, b, offset)).collect())
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let mut possibilities =
+        VecGrid::with_generator(bounds, |_: Coord| (0..tiles.len()).collect::<Vec<_>>());
+    // Rule out the tiles that don't fit next to any tile at all.
+    let all_coords = possibilities
+        .iter()
+        .map(|(coord, _options)| coord)
+        .collect();
+    if !propagate(&mut possibilities, &allowed, all_coords) {
+        return None;
+    }
+
+    loop {
+        // Observe: collapse a random one of the least certain cells.
+        let fewest = possibilities
+            .iter()
+            .map(|(_coord, options)| options.len())
+            .filter(|&count| count > 1)
+            .min();
+        let fewest = match fewest {
+            Some(fewest) => fewest,
+            None => break,
+        };
+        let candidates = possibilities
+            .iter()
+            .filter(|(_coord, options)| options.len() == fewest)
+            .map(|(coord, _options)| coord)
+            .collect::<Vec<_>>();
+        let coord = candidates[rng.gen_range(0..candidates.len())];
+        let options = possibilities.get_mut(coord).unwrap();
+        let choice = options[rng.gen_range(0..options.len())];
+        *options = vec![choice];
+
+        if !propagate(&mut possibilities, &allowed, vec![coord]) {
+            return None;
+        }
+    }
+
+    Some(VecGrid::with_generator(bounds, |coord: Coord| {
+        tiles[possibilities.get(coord).unwrap()[0]].clone()
+    }))
+}
+
+/// Rules out the tiles that no longer fit next to their neighbors, starting
+/// from the neighbors of `changed` and spreading outwards. Returns `false` if
+/// some cell has no possible tiles left.
+fn propagate(
+    possibilities: &mut VecGrid<Vec<usize>>,
+    allowed: &[Vec<Vec<bool>>],
+    mut changed: Vec<Coord>,
+) -> bool {
+    if possibilities
+        .iter()
+        .any(|(_coord, options)| options.is_empty())
+    {
+        return false;
+    }
+    let offsets = Connectivity::Orthogonal.offsets();
+    while let Some(coord) = changed.pop() {
+        let options = possibilities.get(coord).unwrap().clone();
+        for (direction, &offset) in offsets.iter().enumerate() {
+            let neighbor = coord + offset;
+            let neighbor_options = match possibilities.get_mut(neighbor) {
+                Some(neighbor_options) => neighbor_options,
+                None => continue,
+            };
+            let previous_count = neighbor_options.len();
+            neighbor_options.retain(|&b| options.iter().any(|&a| allowed[direction][a][b]));
+            if neighbor_options.is_empty() {
+                return false;
+            }
+            if neighbor_options.len() < previous_count {
+                changed.push(neighbor);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    /// Horizontal neighbors must match and vertical neighbors must differ.
+    fn stripes(a: &char, b: &char, offset: Coord) -> bool {
+        (offset.y == 0) == (a == b)
+    }
+
+    #[test]
+    fn wfc_forms_stripes() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let bounds = Rect::with_corners((-2, -1), (6, 5));
+        let grid = wfc(bounds, &['a', 'b'], stripes, &mut rng).unwrap();
+        assert_eq!(grid.bounds, bounds);
+        for y in bounds.y_range() {
+            let row = grid.get((bounds.left, y)).unwrap();
+            assert!(bounds.x_range().all(|x| grid.get((x, y)) == Some(row)));
+            if y + 1 < bounds.bottom {
+                assert_ne!(grid.get((bounds.left, y + 1)), Some(row));
+            }
+        }
+    }
+
+    #[test]
+    fn wfc_contradiction() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(wfc(Rect::new((3, 3)), &['a'], stripes, &mut rng), None);
+        assert_eq!(wfc(Rect::new((3, 3)), &[], stripes, &mut rng), None);
+        let single = wfc(Rect::new((3, 1)), &['a'], stripes, &mut rng).unwrap();
+        assert!(single.iter().all(|(_coord, &tile)| tile == 'a'));
+    }
+}
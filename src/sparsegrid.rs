@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use crate::{
+    coord::Coord,
+    grid::{Grid, IterCell},
+    patterns::Rect,
+};
+
+/// A grid with no fixed bounds, which only stores the cells that hold
+/// something other than the default value. This suits huge, mostly empty
+/// worlds, where a [`VecGrid`](crate::VecGrid) would waste memory on empty
+/// cells.
+///
+/// Every coord has a cell: unstored cells read as the default value.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coord, T>,
+    /// Returned for cells that aren't stored.
+    default: T,
+}
+
+impl<T: Default> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            default: T::default(),
+        }
+    }
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// The smallest rect containing every cell that isn't the default value,
+    /// or `None` if every cell is the default.
+    ///
+    /// This scans every stored cell, so it takes time proportional to
+    /// [`len`](Self::len).
+    pub fn bounds(&self) -> Option<Rect> {
+        let mut coords = self.iter().map(|(coord, _cell)| coord);
+        let first = coords.next()?;
+        let mut bounds = Rect::with_corners(first, first + Coord::ONE);
+        for coord in coords {
+            bounds.top = bounds.top.min(coord.y);
+            bounds.bottom = bounds.bottom.max(coord.y + 1);
+            bounds.left = bounds.left.min(coord.x);
+            bounds.right = bounds.right.max(coord.x + 1);
+        }
+        Some(bounds)
+    }
+
+    /// The number of cells that aren't the default value.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns an iterator over the cells that aren't the default value, in
+    /// arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = IterCell<'_, T>> {
+        self.cells
+            .iter()
+            .filter(move |(_coord, cell)| **cell != self.default)
+            .map(|(&coord, cell)| (coord, cell))
+    }
+}
+
+/// Grids are equal if every cell holds the same value, regardless of which
+/// default-valued cells happen to be stored.
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default
+            && self.len() == other.len()
+            && self
+                .iter()
+                .all(|(coord, cell)| other.cells.get(&coord) == Some(cell))
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T: Default + PartialEq> Grid<T> for SparseGrid<T> {
+    /// Returns the cell at `coord`, which is the default value if it isn't
+    /// stored. This never returns `None`.
+    fn get<C: Into<Coord>>(&self, coord: C) -> Option<&T> {
+        Some(self.cells.get(&coord.into()).unwrap_or(&self.default))
+    }
+
+    /// Returns the cell at `coord`, storing the default value there first if
+    /// it isn't stored. This never returns `None`.
+    ///
+    /// Since the cell may be left holding the default value, prefer
+    /// [`set`](Grid::set), [`replace`](Grid::replace) or [`take`](Grid::take),
+    /// which don't store default values. Stored default values don't count
+    /// towards [`len`](SparseGrid::len) or [`bounds`](SparseGrid::bounds).
+    fn get_mut<C: Into<Coord>>(&mut self, coord: C) -> Option<&mut T> {
+        Some(self.cells.entry(coord.into()).or_default())
+    }
+
+    fn copy<C1, C2>(&mut self, src: C1, dest: C2) -> bool
+    where
+        T: Copy,
+        C1: Into<Coord>,
+        C2: Into<Coord>,
+    {
+        let (src, dest) = (src.into(), dest.into());
+        if src == dest {
+            return false;
+        }
+        let value = *self.get(src).unwrap();
+        self.set(dest, value)
+    }
+
+    /// Swaps the contents of two cells.
+    fn swap<C1, C2>(&mut self, coord1: C1, coord2: C2) -> bool
+    where
+        C1: Into<Coord>,
+        C2: Into<Coord>,
+    {
+        let (coord1, coord2) = (coord1.into(), coord2.into());
+        if coord1 != coord2 {
+            let value1 = self.take(coord1).unwrap();
+            let value2 = self.replace(coord2, value1).unwrap();
+            self.set(coord1, value2);
+        }
+        true
+    }
+
+    /// Moves the contents of `src` into `dest`, returning the previous contents
+    /// of `dest`.
+    fn mov(&mut self, src: Coord, dest: Coord) -> Option<T>
+    where
+        T: Default + Clone,
+    {
+        if src == dest {
+            return self.get(src).cloned();
+        }
+        let src_value = self.take(src).unwrap();
+        self.replace(dest, src_value)
+    }
+
+    /// Stores `value` at `coord`, or removes the stored cell if `value` is the
+    /// default value. This never returns `false`.
+    fn set<C: Into<Coord>>(&mut self, coord: C, value: T) -> bool {
+        self.replace(coord, value);
+        true
+    }
+
+    /// Like [`set`](Grid::set), but returns the previous contents of the cell.
+    fn replace<C: Into<Coord>>(&mut self, coord: C, value: T) -> Option<T> {
+        let coord = coord.into();
+        let previous = if value == self.default {
+            self.cells.remove(&coord)
+        } else {
+            self.cells.insert(coord, value)
+        };
+        Some(previous.unwrap_or_default())
+    }
+
+    fn take<C: Into<Coord>>(&mut self, coord: C) -> Option<T>
+    where
+        T: Default,
+    {
+        Some(self.cells.remove(&coord.into()).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scattered_cells() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.set((-1000, 20), 'a');
+        grid.set((3, -5000), 'b');
+        grid.set((0, 0), 'c');
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get((-1000, 20)), Some(&'a'));
+        assert_eq!(grid.get((3, -5000)), Some(&'b'));
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::with_corners((-1000, -5000), (4, 21)))
+        );
+    }
+
+    #[test]
+    fn unset_cells_are_default() {
+        let mut grid = SparseGrid::<u8>::new();
+        assert_eq!(grid.get((7, 7)), Some(&0));
+        assert!(grid.is_empty());
+        assert_eq!(grid.replace((7, 7), 1), Some(0));
+        assert_eq!(grid.take((7, 7)), Some(1));
+        assert_eq!(grid.get((7, 7)), Some(&0));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn default_writes_are_not_stored() {
+        let mut grid = SparseGrid::<u8>::new();
+        assert_eq!(grid.take((5, 5)), Some(0));
+        grid.set((-3, 2), 0);
+        grid.mov(Coord::new(9, 9), Coord::new(8, 8));
+        assert!(grid.swap((1, 1), (2, 2)));
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid, SparseGrid::new());
+
+        grid.set((4, 4), 6);
+        grid.set((4, 4), 0);
+        assert!(grid.is_empty());
+        assert_eq!(grid, SparseGrid::new());
+
+        // Cells left at the default through `get_mut` are ignored.
+        *grid.get_mut((1, 2)).unwrap() = 3;
+        *grid.get_mut((1, 2)).unwrap() = 0;
+        assert_eq!(grid.len(), 0);
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid, SparseGrid::new());
+    }
+
+    #[test]
+    fn copy_swap_and_move() {
+        let mut grid = SparseGrid::new();
+        grid.set((0, 0), 1);
+        grid.set((1, 0), 2);
+        assert!(grid.copy((0, 0), (5, 5)));
+        assert!(!grid.copy((5, 5), (5, 5)));
+        assert_eq!(grid.get((5, 5)), Some(&1));
+        assert!(grid.swap((0, 0), (1, 0)));
+        assert_eq!(grid.get((0, 0)), Some(&2));
+        assert_eq!(grid.get((1, 0)), Some(&1));
+        assert_eq!(grid.mov(Coord::new(1, 0), Coord::new(0, 0)), Some(2));
+        assert_eq!(grid.get((0, 0)), Some(&1));
+        assert_eq!(grid.get((1, 0)), Some(&0));
+        assert_eq!(grid.mov(Coord::new(0, 0), Coord::new(0, 0)), Some(1));
+        assert_eq!(grid.get((0, 0)), Some(&1));
+    }
+}
@@ -1,22 +1,55 @@
+mod automata;
+mod chunk;
+mod heightmap;
+mod region;
+mod text;
+mod transform;
+
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt,
+    hash::Hash,
 };
 
+use rand::Rng;
+
 use crate::{
-    coord::Coord,
+    coord::{Col, Coord, Row},
     grid::{Grid, GridError, IterCell, IterCellMut},
-    patterns::{Neighborhood, Rect},
+    patterns::{Connectivity, Neighborhood, Rect},
 };
 
 /// The core type of this library. A 2D grid of cell type `T`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "VecGridData<T>")
+)]
 pub struct VecGrid<T> {
     /// Row-major, linear storage of cell data.
     pub cells: Vec<T>,
     pub bounds: Rect,
 }
 
+/// The unvalidated serialized form of a [`VecGrid`], which is checked by
+/// [`VecGrid::from_parts`] when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct VecGridData<T> {
+    cells: Vec<T>,
+    bounds: Rect,
+}
+
+#[cfg(feature = "serde")]
+impl<T> std::convert::TryFrom<VecGridData<T>> for VecGrid<T> {
+    type Error = GridError;
+
+    fn try_from(data: VecGridData<T>) -> Result<Self, Self::Error> {
+        VecGrid::from_parts(data.bounds, data.cells)
+    }
+}
+
 impl<T> Grid<T> for VecGrid<T> {
     fn get<C: Into<Coord>>(&self, coord: C) -> Option<&T> {
         self.cells.get(self.coord_to_index(coord)?)
@@ -35,6 +68,9 @@ impl<T> Grid<T> for VecGrid<T> {
     {
         if let Some(src_index) = self.coord_to_index(src) {
             if let Some(dest_index) = self.coord_to_index(dest) {
+                if src_index == dest_index {
+                    return false;
+                }
                 self.cells
                     .copy_within(src_index..(src_index + 1), dest_index);
                 return true;
@@ -62,12 +98,15 @@ impl<T> Grid<T> for VecGrid<T> {
     /// of `dest`.
     fn mov(&mut self, src: Coord, dest: Coord) -> Option<T>
     where
-        T: Default,
+        T: Default + Clone,
     {
         // Make sure both coordinates are in bounds before mutating things.
-        if !(self.bounds.contains(src) && self.bounds.contains(dest)) {
+        if self.coord_to_index(src).is_none() || self.coord_to_index(dest).is_none() {
             return None;
         }
+        if src == dest {
+            return self.get(src).cloned();
+        }
         let src_value = self.take(src).unwrap();
         self.replace(dest, src_value)
     }
@@ -99,6 +138,52 @@ impl<T> VecGrid<T> {
         Self { cells, bounds }
     }
 
+    /// Wraps existing row-major cell data in a grid without copying it.
+    ///
+    /// Returns [`GridError::InvalidBounds`](GridError::InvalidBounds) if the
+    /// number of cells doesn't match the area of `bounds`.
+    pub fn from_parts(bounds: Rect, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != bounds.area() as usize {
+            return Err(GridError::InvalidBounds);
+        }
+        Ok(Self { cells, bounds })
+    }
+
+    /// Sets every cell to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for cell in self.cells.iter_mut() {
+            *cell = value.clone();
+        }
+    }
+
+    /// Sets every cell within `rect` to `value`. Any part of `rect` outside of
+    /// the grid bounds is ignored.
+    pub fn fill_rect(&mut self, rect: Rect, value: T)
+    where
+        T: Clone,
+    {
+        let x_range = rect.left.max(self.bounds.left)..rect.right.min(self.bounds.right);
+        for y in rect.top.max(self.bounds.top)..rect.bottom.min(self.bounds.bottom) {
+            for x in x_range.clone() {
+                self.set((x, y), value.clone());
+            }
+        }
+    }
+
+    /// Resets every cell to its default value in place, keeping the existing
+    /// bounds and allocation.
+    pub fn clear(&mut self)
+    where
+        T: Default,
+    {
+        for cell in self.cells.iter_mut() {
+            *cell = T::default();
+        }
+    }
+
     /// Copies all values of `other` into `self` at offset `position`.
     pub fn embed<C>(&mut self, content: VecGrid<T>, position: C)
     where
@@ -110,11 +195,213 @@ impl<T> VecGrid<T> {
         }
     }
 
+    /// Stamps the cells of `stamp` onto `self`, offset by `at`, by calling
+    /// `blend` with each destination cell and the stamp cell landing on it.
+    /// Stamp cells landing outside of the bounds are ignored.
+    ///
+    /// Unlike [`embed`](Self::embed), `blend` decides how the cells combine,
+    /// such as to skip "transparent" stamp cells.
+    pub fn overlay(&mut self, stamp: &VecGrid<T>, at: Coord, blend: impl Fn(&mut T, &T)) {
+        for (coord, stamp_cell) in stamp.iter() {
+            if let Some(cell) = self.get_mut(coord + at) {
+                blend(cell, stamp_cell);
+            }
+        }
+    }
+
+    /// Changes the bounds of the grid to `new_bounds`, keeping the contents of
+    /// cells within both the old and new bounds at the same coords. Cells
+    /// outside of the new bounds are dropped, and newly exposed cells are set
+    /// to their default value.
+    pub fn resize(&mut self, new_bounds: Rect)
+    where
+        T: Default + Clone,
+    {
+        let mut resized = VecGrid::new(new_bounds);
+        if let Some(overlap) = self.bounds.intersection(&new_bounds) {
+            for y in overlap.y_range() {
+                for x in overlap.x_range() {
+                    let value = self.take((x, y)).unwrap();
+                    resized.set((x, y), value);
+                }
+            }
+        }
+        *self = resized;
+    }
+
+    /// Copies the cells within `rect` into a new grid, whose bounds are the
+    /// part of `rect` within the bounds of `self`. If they don't overlap at
+    /// all, the new grid has zero area.
+    pub fn crop(&self, rect: Rect) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let bounds = self
+            .bounds
+            .intersection(&rect)
+            .unwrap_or_else(|| Rect::with_corners(rect.offset(), rect.offset()));
+        VecGrid::with_generator(bounds, |coord: Coord| self.get(coord).unwrap().clone())
+    }
+
+    /// Returns a copy of the grid enlarged by `margin` cells on every side,
+    /// with the new border cells set to `fill`. The original cells keep their
+    /// coords, so the bounds grow outwards from the original bounds. A negative
+    /// `margin` is treated as 0.
+    pub fn pad(&self, margin: i32, fill: T) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let margin = Coord::new(margin.max(0), margin.max(0));
+        let bounds = Rect::with_corners(
+            self.bounds.offset() - margin,
+            self.bounds.offset() + self.bounds.dimensions() + margin,
+        );
+        VecGrid::with_generator(bounds, |coord: Coord| {
+            self.get(coord).unwrap_or(&fill).clone()
+        })
+    }
+
+    /// Returns the smallest rect containing every cell that isn't equal to the
+    /// default value, or `None` if every cell is the default.
+    pub fn content_bounds(&self) -> Option<Rect>
+    where
+        T: Default + PartialEq,
+    {
+        let default = T::default();
+        let mut content_coords = self
+            .iter()
+            .filter(|&(_coord, cell)| *cell != default)
+            .map(|(coord, _cell)| coord);
+        let first = content_coords.next()?;
+        let mut bounds = Rect::with_corners(first, first + Coord::ONE);
+        for coord in content_coords {
+            bounds.left = bounds.left.min(coord.x);
+            bounds.right = bounds.right.max(coord.x + 1);
+            bounds.bottom = bounds.bottom.max(coord.y + 1);
+        }
+        Some(bounds)
+    }
+
+    /// Returns a new grid with the same bounds, where each cell is the result
+    /// of applying `f` to the corresponding cell of `self`.
     pub fn map<U, F>(&self, f: F) -> VecGrid<U>
     where
         F: Fn(&T) -> U,
     {
-        VecGrid::<U>::with_generator(self.bounds, |coord: Coord| f(self.get(coord).unwrap()))
+        VecGrid {
+            cells: self.cells.iter().map(f).collect(),
+            bounds: self.bounds,
+        }
+    }
+
+    /// Returns a new grid with the same bounds, where each cell is the result
+    /// of applying `f` to the corresponding cells of `self` and `other`.
+    ///
+    /// Returns [`GridError::InvalidBounds`](GridError::InvalidBounds) if the
+    /// grids don't have identical bounds.
+    pub fn zip_map<U, V>(
+        &self,
+        other: &VecGrid<U>,
+        f: impl Fn(&T, &U) -> V,
+    ) -> Result<VecGrid<V>, GridError> {
+        if self.bounds != other.bounds {
+            return Err(GridError::InvalidBounds);
+        }
+        Ok(VecGrid {
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            bounds: self.bounds,
+        })
+    }
+
+    /// Returns a new grid with the same bounds, where each cell is the value
+    /// `table` maps the corresponding cell of `self` to, or `default` if it
+    /// has no entry.
+    pub fn remap<U>(&self, table: &HashMap<T, U>, default: U) -> VecGrid<U>
+    where
+        T: Hash + Eq,
+        U: Clone,
+    {
+        self.map(|cell| table.get(cell).unwrap_or(&default).clone())
+    }
+
+    /// Returns the cell at `coord` on a torus, wrapping coords outside of the
+    /// bounds around to the opposite edge (see
+    /// [`wrap_coord`](Self::wrap_coord)).
+    ///
+    /// To count neighbors across the edges, such as for cellular automata,
+    /// pass each coord of a [`Neighborhood`] through this instead of `get`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid has zero area.
+    pub fn get_wrapping<C: Into<Coord>>(&self, coord: C) -> &T {
+        let index = self.coord_to_index(self.wrap_coord(coord)).unwrap();
+        &self.cells[index]
+    }
+
+    /// The mutable version of [`get_wrapping`](Self::get_wrapping).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid has zero area.
+    pub fn get_wrapping_mut<C: Into<Coord>>(&mut self, coord: C) -> &mut T {
+        let index = self.coord_to_index(self.wrap_coord(coord)).unwrap();
+        &mut self.cells[index]
+    }
+
+    /// Maps `coord` into the bounds using the euclidean remainder of its
+    /// distance from the top-left corner, so that one step past the right
+    /// edge lands on the left edge, and so on.
+    pub fn wrap_coord<C: Into<Coord>>(&self, coord: C) -> Coord {
+        let local = coord.into() - self.bounds.offset();
+        self.bounds.offset()
+            + Coord::new(
+                local.x.rem_euclid(self.bounds.width()),
+                local.y.rem_euclid(self.bounds.height()),
+            )
+    }
+
+    /// Returns mutable references to the cells at each of `coords` at once.
+    ///
+    /// Returns [`GridError::OutOfBounds`](GridError::OutOfBounds) for the first
+    /// coord outside of the bounds, or
+    /// [`GridError::AlreadyVisited`](GridError::AlreadyVisited) for the first
+    /// coord that appears more than once.
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        coords: [Coord; N],
+    ) -> Result<[&mut T; N], GridError> {
+        let mut indices = [0; N];
+        for (i, &coord) in coords.iter().enumerate() {
+            indices[i] = self
+                .coord_to_index(coord)
+                .ok_or(GridError::OutOfBounds(coord))?;
+            if indices[..i].contains(&indices[i]) {
+                return Err(GridError::AlreadyVisited(coord));
+            }
+        }
+        Ok(self
+            .cells
+            .get_disjoint_mut(indices)
+            .expect("indices are in bounds and distinct"))
+    }
+
+    /// Returns the contiguous cell data of the full rows from `y_start` up to
+    /// (but not including) `y_end`, or `None` if the range isn't within the
+    /// grid bounds.
+    pub fn rows_slice(&self, y_start: i32, y_end: i32) -> Option<&[T]> {
+        if y_start > y_end || y_start < self.bounds.top || y_end > self.bounds.bottom {
+            return None;
+        }
+        let width = self.bounds.width();
+        let start = ((y_start - self.bounds.top) * width) as usize;
+        let end = ((y_end - self.bounds.top) * width) as usize;
+        Some(&self.cells[start..end])
     }
 
     /// Returns an iterator over all cells in the grid.
@@ -137,8 +424,161 @@ impl<T> VecGrid<T> {
             .map(move |(index, cell)| (Self::index_to_coord_with_bounds(rect, index), cell))
     }
 
+    /// Returns the number of cells that satisfy `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    /// Returns the coord of the first cell in row-major order that satisfies
+    /// `predicate`, or `None` if no cell does.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.index_to_coord(index))
+    }
+
+    /// Returns the in-bounds orthogonal and diagonal (Moore) neighbors of
+    /// `coord`, in [`Neighborhood::iter`] order.
+    pub fn neighbors<C: Into<Coord>>(&self, coord: C) -> impl Iterator<Item = IterCell<'_, T>> {
+        Neighborhood::new(coord)
+            .into_iter()
+            .filter_map(move |neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// Returns the in-bounds orthogonal (Von Neumann) neighbors of `coord`, in
+    /// [`Neighborhood::iter_ortho`] order.
+    pub fn neighbors_ortho<C: Into<Coord>>(
+        &self,
+        coord: C,
+    ) -> impl Iterator<Item = IterCell<'_, T>> {
+        Neighborhood::new(coord)
+            .into_iter_ortho()
+            .filter_map(move |neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// Returns a bitmask of which of the Moore neighbors of `coord` satisfy
+    /// `matches`, as used for autotiling. Bit `i` corresponds to the `i`th
+    /// neighbor of [`Neighborhood::iter`](Neighborhood::iter), starting from
+    /// north and continuing clockwise. Out of bounds neighbors never match.
+    pub fn neighbor_bitmask<C: Into<Coord>>(&self, coord: C, matches: impl Fn(&T) -> bool) -> u8 {
+        Neighborhood::new(coord)
+            .iter()
+            .enumerate()
+            .filter(|&(_bit, neighbor)| self.get(neighbor).is_some_and(&matches))
+            .fold(0, |mask, (bit, _neighbor)| mask | (1 << bit))
+    }
+
+    /// Returns an iterator over all cells in the grid along with their
+    /// [`neighbor_bitmask`](Self::neighbor_bitmask).
+    ///
+    /// `matches` is evaluated once per cell up front, rather than once per
+    /// neighbor lookup.
+    pub fn iter_with_masks(
+        &self,
+        matches: impl Fn(&T) -> bool,
+    ) -> impl Iterator<Item = (Coord, &T, u8)> {
+        let matched = self.cells.iter().map(matches).collect::<Vec<_>>();
+        self.iter().map(move |(coord, cell)| {
+            let mask = Neighborhood::new(coord)
+                .iter()
+                .enumerate()
+                .filter(|&(_bit, neighbor)| {
+                    self.coord_to_index(neighbor)
+                        .is_some_and(|index| matched[index])
+                })
+                .fold(0, |mask, (bit, _neighbor)| mask | (1 << bit));
+            (coord, cell, mask)
+        })
+    }
+
+    /// Returns an iterator over the cells of row `y` from left to right, or
+    /// `None` if the row is out of bounds.
+    pub fn row(&self, Row(y): Row) -> Option<impl Iterator<Item = IterCell<'_, T>>> {
+        let cells = self.rows_slice(y, y + 1)?;
+        Some(
+            self.bounds
+                .x_range()
+                .zip(cells)
+                .map(move |(x, cell)| (Coord::new(x, y), cell)),
+        )
+    }
+
+    /// Returns an iterator over the cells of column `x` in order of increasing
+    /// `y`, or `None` if the column is out of bounds.
+    pub fn column(&self, Col(x): Col) -> Option<impl Iterator<Item = IterCell<'_, T>>> {
+        if !self.bounds.x_range().contains(&x) {
+            return None;
+        }
+        Some(self.bounds.y_range().map(move |y| {
+            let coord = Coord::new(x, y);
+            (coord, self.get(coord).unwrap())
+        }))
+    }
+
+    /// Like [`row`](Self::row), but with a plain `y` coordinate. This is a
+    /// separate method, rather than `row` accepting an `i32`, so that an `x`
+    /// can't be passed to `row` by mistake.
+    pub fn row_at(&self, y: i32) -> Option<impl Iterator<Item = IterCell<'_, T>>> {
+        self.row(Row(y))
+    }
+
+    /// Like [`column`](Self::column), but with a plain `x` coordinate. See
+    /// [`row_at`](Self::row_at).
+    pub fn col_at(&self, x: i32) -> Option<impl Iterator<Item = IterCell<'_, T>>> {
+        self.column(Col(x))
+    }
+
+    /// Returns an iterator over each row of the grid (see
+    /// [`row`](Self::row)), in order of increasing `y`.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = IterCell<'_, T>>> + '_ {
+        self.bounds
+            .y_range()
+            .map(move |y| self.row(Row(y)).unwrap())
+    }
+
+    /// Returns an iterator over the cells in Morton (Z-order) order, which
+    /// keeps nearby cells close together in the sequence.
+    ///
+    /// The curve covers the smallest power-of-two square containing the grid,
+    /// anchored at its top-left corner, with coords outside of the bounds
+    /// skipped.
+    pub fn iter_morton(&self) -> impl Iterator<Item = IterCell<'_, T>> {
+        let side =
+            (self.bounds.width().max(self.bounds.height()).max(0) as u64).next_power_of_two();
+        (0..side * side)
+            .map(move |index| {
+                let local = Coord::new(
+                    compact_even_bits(index) as i32,
+                    compact_even_bits(index >> 1) as i32,
+                );
+                self.bounds.offset() + local
+            })
+            .filter_map(move |coord| self.get(coord).map(|cell| (coord, cell)))
+    }
+
+    /// Returns an iterator over the anti-diagonals of the grid, where each
+    /// item holds the cells sharing the same `x + y`, ordered by increasing `x`.
+    /// The anti-diagonals are visited in order of increasing `x + y`, so each
+    /// cell comes after its west and south neighbors (wavefront order).
+    pub fn iter_antidiagonals(&self) -> impl Iterator<Item = Vec<IterCell<'_, T>>> {
+        let bounds = self.bounds;
+        let sums = if bounds.area() > 0 {
+            (bounds.left + bounds.top)..(bounds.right + bounds.bottom - 1)
+        } else {
+            0..0
+        };
+        sums.map(move |sum| {
+            bounds
+                .x_range()
+                .map(|x| Coord::new(x, sum - x))
+                .filter_map(|coord| self.get(coord).map(|cell| (coord, cell)))
+                .collect()
+        })
+    }
+
     /// Returns an iterator over the cells specified by the coords iterator.
-    pub fn selection_iter<I>(&self, coords: I) -> SelectionIter<T, I>
+    pub fn selection_iter<I>(&self, coords: I) -> SelectionIter<'_, T, I>
     where
         I: Iterator<Item = Coord>,
     {
@@ -152,14 +592,18 @@ impl<T> VecGrid<T> {
     /// create multiple simultaneous mutable references to the cell), a
     /// [`GridError::AlreadyVisited`](GridError::AlreadyVisited) will be returned
     /// in place of the cell contents.
-    pub fn selection_iter_mut<I>(&mut self, coords: I) -> SelectionIterMut<T, I>
+    pub fn selection_iter_mut<I>(&mut self, coords: I) -> SelectionIterMut<'_, T, I>
     where
         I: Iterator<Item = Coord>,
     {
+        let mut unvisited = BTreeMap::new();
+        if !self.cells.is_empty() {
+            unvisited.insert(0, &mut self.cells[..]);
+        }
         SelectionIterMut {
-            grid: self,
+            bounds: self.bounds,
+            unvisited,
             coords,
-            visited_coords: HashSet::new(),
         }
     }
 
@@ -177,26 +621,84 @@ impl<T> VecGrid<T> {
         &self,
         starting_coord: C,
         predicate: impl Fn(&T) -> bool + 'static,
-    ) -> FloodIter<T> {
+    ) -> FloodIter<'_, T> {
+        self.flood_iter_with(starting_coord, predicate, FloodOptions::default())
+    }
+
+    /// Like [`flood_iter`](Self::flood_iter), but with control over how the
+    /// fill spreads.
+    pub fn flood_iter_with<C: Into<Coord>>(
+        &self,
+        starting_coord: C,
+        predicate: impl Fn(&T) -> bool + 'static,
+        options: FloodOptions,
+    ) -> FloodIter<'_, T> {
         let mut coords_to_search = VecDeque::new();
-        coords_to_search.push_back(starting_coord.into());
+        coords_to_search.push_back((starting_coord.into(), 0));
 
         FloodIter {
             grid: self,
             predicate: Box::new(predicate),
+            options,
             searched_coords: vec![],
             coords_to_search,
         }
     }
 
+    /// Fills outward from `start` with `fill`, like a paint bucket, stopping
+    /// at cells equal to `boundary` rather than at cells that differ from the
+    /// starting cell. Returns the number of cells filled.
+    pub fn boundary_fill<C: Into<Coord>>(&mut self, start: C, boundary: &T, fill: T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut visited = vec![false; self.cells.len()];
+        let mut coords_to_fill = VecDeque::new();
+        coords_to_fill.push_back(start.into());
+        let mut fill_count = 0;
+
+        while let Some(coord) = coords_to_fill.pop_front() {
+            let index = match self.coord_to_index(coord) {
+                Some(index) if !visited[index] => index,
+                _ => continue,
+            };
+            visited[index] = true;
+            if self.cells[index] == *boundary {
+                continue;
+            }
+            self.cells[index] = fill.clone();
+            fill_count += 1;
+            coords_to_fill.extend(Neighborhood::new(coord).iter_ortho());
+        }
+        fill_count
+    }
+
+    /// Returns a random coord at which a rect of `size` could be placed (as
+    /// its top-left corner) while still fitting entirely within the grid
+    /// bounds, or `None` if `size` is larger than the grid.
+    pub fn random_placement(&self, size: Coord, rng: &mut impl Rng) -> Option<Coord> {
+        let max_x = self.bounds.width() - size.x;
+        let max_y = self.bounds.height() - size.y;
+        if size.x < 0 || size.y < 0 || max_x < 0 || max_y < 0 {
+            return None;
+        }
+        let offset = Coord::new(rng.gen_range(0..=max_x), rng.gen_range(0..=max_y));
+        Some(self.bounds.offset() + offset)
+    }
+
     /// Converts a 2D Grid coordinate into a linear Vec index.
     fn coord_to_index<C: Into<Coord>>(&self, coord: C) -> Option<usize> {
-        let coord = coord.into();
-        if !self.bounds.contains(coord) {
+        Self::coord_to_index_with_bounds(self.bounds, coord.into())
+    }
+
+    /// Use `coord_to_index` if possible. This exists so that
+    /// `SelectionIterMut` can avoid borrowing the grid.
+    fn coord_to_index_with_bounds(bounds: Rect, coord: Coord) -> Option<usize> {
+        if !bounds.contains(coord) {
             return None;
         }
-        let offset_coord = coord - self.bounds.offset();
-        Some((offset_coord.x + offset_coord.y * self.bounds.width()) as usize)
+        let offset_coord = coord - bounds.offset();
+        Some((offset_coord.x + offset_coord.y * bounds.width()) as usize)
     }
 
     /// Converts a linear Vec index into a 2D Grid coordinate.
@@ -208,11 +710,22 @@ impl<T> VecGrid<T> {
     /// avoid borrowing `self`.
     fn index_to_coord_with_bounds(bounds: Rect, index: usize) -> Coord {
         let y = (index as f32 / bounds.width() as f32).floor() as i32;
-        let x = index as i32 - (y * bounds.width()) as i32;
+        let x = index as i32 - y * bounds.width();
         Coord::new(x, y) + bounds.offset()
     }
 }
 
+/// Collects every other bit of `n`, starting from the lowest, into the low
+/// half of the result. This undoes the bit interleaving of a Morton index.
+fn compact_even_bits(n: u64) -> u64 {
+    let mut n = n & 0x5555_5555_5555_5555;
+    n = (n | (n >> 1)) & 0x3333_3333_3333_3333;
+    n = (n | (n >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    n = (n | (n >> 4)) & 0x00ff_00ff_00ff_00ff;
+    n = (n | (n >> 8)) & 0x0000_ffff_0000_ffff;
+    (n | (n >> 16)) & 0x0000_0000_ffff_ffff
+}
+
 pub struct SelectionIter<'a, T, I> {
     // TODO: Generic Grid
     grid: &'a VecGrid<T>,
@@ -237,10 +750,12 @@ where
 }
 
 pub struct SelectionIterMut<'a, T, I> {
-    // TODO: Generic Grid
-    grid: &'a mut VecGrid<T>,
+    bounds: Rect,
+    /// The runs of cells that haven't been visited yet, keyed by the index of
+    /// their first cell. Visiting a cell splits it out of its run, so it can
+    /// only be handed out once.
+    unvisited: BTreeMap<usize, &'a mut [T]>,
     coords: I,
-    visited_coords: HashSet<Coord>,
 }
 
 impl<'a, T, I> Iterator for SelectionIterMut<'a, T, I>
@@ -250,47 +765,70 @@ where
     type Item = Result<IterCellMut<'a, T>, GridError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(coord) = self.coords.next() {
-            if self.visited_coords.contains(&coord) {
-                return Some(Err(GridError::AlreadyVisited(coord)));
-            }
-            if let Some(cell) = self.grid.get_mut(coord).map(|cell| cell as *mut T) {
-                // SAFETY: We guarantee that only one mut reference to a cell
-                // will be given out at a time by checking each against a list
-                // of visited cells and only returning those that havent already
-                // visited.
-                // This is likely not actually completely safe, given that I
-                // don't know which cases to look out for.
-                let opt_cell = unsafe { cell.as_mut() };
-                if let Some(cell) = opt_cell {
-                    self.visited_coords.insert(coord);
-                    return Some(Ok((coord, cell)));
-                }
-            }
-            return Some(Err(GridError::OutOfBounds(coord)));
+        let coord = self.coords.next()?;
+        let index = match VecGrid::<T>::coord_to_index_with_bounds(self.bounds, coord) {
+            Some(index) => index,
+            None => return Some(Err(GridError::OutOfBounds(coord))),
+        };
+        let start = match self.unvisited.range(..=index).next_back() {
+            Some((&start, run)) if index < start + run.len() => start,
+            _ => return Some(Err(GridError::AlreadyVisited(coord))),
+        };
+        let run = self.unvisited.remove(&start).unwrap();
+        let (before, rest) = run.split_at_mut(index - start);
+        let (cell, after) = rest.split_first_mut().unwrap();
+        if !before.is_empty() {
+            self.unvisited.insert(start, before);
         }
-        None
+        if !after.is_empty() {
+            self.unvisited.insert(index + 1, after);
+        }
+        Some(Ok((coord, cell)))
     }
 }
 
+/// Controls how far and in which directions [`VecGrid::flood_iter_with`]
+/// spreads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FloodOptions {
+    /// Whether the fill also spreads between diagonally adjacent cells.
+    pub diagonal: bool,
+    /// The maximum number of steps away from the starting coord the fill may
+    /// spread, or `None` to spread until no matching cells remain.
+    pub max_distance: Option<u32>,
+}
+
+/// How [`VecGrid::convolve`] samples coords outside of the grid bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeMode {
+    /// Use the nearest cell on the edge of the grid.
+    Clamp,
+    /// Wrap around to the opposite edge (see [`VecGrid::wrap_coord`]).
+    Wrap,
+    /// Treat the cell as having a value of zero.
+    Zero,
+}
+
 pub struct FloodIter<'a, T> {
     // TODO: Generic Grid
     grid: &'a VecGrid<T>,
     predicate: Box<dyn Fn(&T) -> bool>,
+    options: FloodOptions,
     searched_coords: Vec<Coord>,
-    coords_to_search: VecDeque<Coord>,
+    /// Coords along with their distance in steps from the starting coord.
+    coords_to_search: VecDeque<(Coord, u32)>,
 }
 
 impl<'a, T> Iterator for FloodIter<'a, T> {
     type Item = IterCell<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.coords_to_search.len() > 0 {
-            let coord = self.coords_to_search.pop_front().unwrap();
+        while !self.coords_to_search.is_empty() {
+            let (coord, distance) = self.coords_to_search.pop_front().unwrap();
             let is_cell_included = self
                 .grid
                 .get(coord)
-                .and_then(|cell| Some((self.predicate)(cell)))
+                .map(|cell| (self.predicate)(cell))
                 .unwrap_or(false);
 
             self.searched_coords.push(coord);
@@ -299,16 +837,33 @@ impl<'a, T> Iterator for FloodIter<'a, T> {
                 continue;
             }
 
-            let neighbor_coords = Neighborhood::new(coord)
-                .iter_ortho()
-                .filter(|&coord| {
-                    !(self.searched_coords.contains(&coord)
-                        || self.coords_to_search.contains(&coord))
-                        && self.grid.bounds.contains(coord)
-                })
-                .collect::<Vec<Coord>>();
+            let can_spread = self
+                .options
+                .max_distance
+                .is_none_or(|max_distance| distance < max_distance);
+            if can_spread {
+                let connectivity = if self.options.diagonal {
+                    Connectivity::Moore
+                } else {
+                    Connectivity::Orthogonal
+                };
+                let neighbor_coords = connectivity
+                    .offsets()
+                    .iter()
+                    .map(|&offset| coord + offset)
+                    .filter(|&coord| {
+                        !(self.searched_coords.contains(&coord)
+                            || self
+                                .coords_to_search
+                                .iter()
+                                .any(|&(search_coord, _distance)| search_coord == coord))
+                            && self.grid.bounds.contains(coord)
+                    })
+                    .map(|coord| (coord, distance + 1))
+                    .collect::<Vec<_>>();
 
-            self.coords_to_search.extend(neighbor_coords);
+                self.coords_to_search.extend(neighbor_coords);
+            }
 
             return Some((coord, self.grid.get(coord).unwrap()));
         }
@@ -325,13 +880,9 @@ impl fmt::Display for VecGrid<String> {
             for x in self.bounds.x_range() {
                 let s = self.get((x, y)).unwrap_or(default);
 
-                if let Err(e) = write!(f, "{}", s) {
-                    return Err(e);
-                }
-            }
-            if let Err(e) = write!(f, "\n") {
-                return Err(e);
+                write!(f, "{}", s)?;
             }
+            writeln!(f)?;
         }
         Ok(())
     }
@@ -339,8 +890,28 @@ impl fmt::Display for VecGrid<String> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let grid = VecGrid::with_generator(Rect::with_corners((-2, 1), (3, 4)), |(x, y)| x * y);
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(serde_json::from_str::<VecGrid<i32>>(&json).unwrap(), grid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_mismatched_cells() {
+        let json = r#"{"cells":[1,2,3],"bounds":{"top":0,"bottom":2,"left":0,"right":2}}"#;
+        let error = serde_json::from_str::<VecGrid<i32>>(json).unwrap_err();
+        assert!(error.to_string().contains("bounds don't match"));
+    }
+
     #[test]
     fn bounds_and_dimensions() {
         let grid = VecGrid::<()>::new(Rect::new((8, 8)));
@@ -360,6 +931,568 @@ mod tests {
         assert_eq!(grid.index_to_coord(12), Coord::new(4, 1));
     }
 
+    #[test]
+    fn from_parts_matching_length() {
+        let grid = VecGrid::from_parts(Rect::new((3, 2)), vec![0, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(grid.get((2, 0)), Some(&2));
+        assert_eq!(grid.get((0, 1)), Some(&3));
+    }
+
+    #[test]
+    fn from_parts_mismatched_length() {
+        let grid = VecGrid::from_parts(Rect::new((3, 2)), vec![0, 1, 2]);
+        assert_eq!(grid, Err(GridError::InvalidBounds));
+    }
+
+    #[test]
+    fn in_bounds_neighbors() {
+        let grid =
+            VecGrid::with_generator(Rect::with_corners((-1, -1), (2, 2)), |(x, y)| x + 10 * y);
+        let corner = grid.neighbors((-1, -1)).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![
+                (Coord::new(-1, 0), &-1),
+                (Coord::new(0, 0), &0),
+                (Coord::new(0, -1), &-10),
+            ]
+        );
+        assert_eq!(grid.neighbors((0, 0)).count(), 8);
+        assert_eq!(grid.neighbors((5, 5)).count(), 0);
+
+        assert_eq!(grid.neighbors_ortho((-1, -1)).count(), 2);
+        assert_eq!(grid.neighbors_ortho((0, 0)).count(), 4);
+        assert_eq!(
+            grid.neighbors_ortho((1, 0))
+                .map(|(coord, _cell)| coord)
+                .collect::<Vec<_>>(),
+            vec![Coord::new(1, 1), Coord::new(1, -1), Coord::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn neighbor_bitmask_edges() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        grid.set((1, 2), true);
+        grid.set((2, 1), true);
+        // North and east of the center.
+        assert_eq!(grid.neighbor_bitmask((1, 1), |&cell| cell), 0b101);
+        // Out of bounds neighbors of a corner never match.
+        assert_eq!(grid.neighbor_bitmask((0, 0), |&cell| !cell), 0b111);
+    }
+
+    #[test]
+    fn iter_with_masks_matches_neighbor_bitmask() {
+        let grid = VecGrid::with_generator(Rect::new((5, 4)), |(x, y)| (x * 3 + y) % 4 == 0);
+        for (coord, &cell, mask) in grid.iter_with_masks(|&cell| cell) {
+            assert_eq!(grid.get(coord), Some(&cell));
+            assert_eq!(mask, grid.neighbor_bitmask(coord, |&cell| cell));
+        }
+    }
+
+    #[test]
+    fn fill_all_cells() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((3, 3)));
+        grid.fill(7);
+        assert!(grid.iter().all(|(_coord, &cell)| cell == 7));
+    }
+
+    #[test]
+    fn fill_sub_rect() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((6, 6)));
+        let rect = Rect::with_corners((1, 2), (4, 5));
+        grid.fill_rect(rect, 1);
+        for (coord, &cell) in grid.iter() {
+            assert_eq!(cell == 1, rect.contains(coord));
+        }
+    }
+
+    #[test]
+    fn fill_rect_clips_to_bounds() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((4, 4)));
+        grid.fill_rect(Rect::with_corners((-2, 2), (2, 8)), 1);
+        assert_eq!(grid.iter().filter(|(_coord, &cell)| cell == 1).count(), 4);
+        assert_eq!(grid.get((1, 3)), Some(&1));
+        assert_eq!(grid.get((2, 3)), Some(&0));
+    }
+
+    #[test]
+    fn clear_resets_cells() {
+        let bounds = Rect::with_corners((-2, -2), (3, 2));
+        let mut grid = VecGrid::with_generator(bounds, |(x, y)| x * y + 1);
+        grid.clear();
+        assert_eq!(grid.bounds, bounds);
+        assert!(grid.iter().all(|(_coord, &cell)| cell == 0));
+    }
+
+    #[test]
+    fn map_bool_to_char() {
+        let bounds = Rect::with_corners((-1, -1), (3, 2));
+        let grid = VecGrid::with_generator(bounds, |(x, y)| x == y);
+        let char_grid = grid.map(|&cell| if cell { '#' } else { '.' });
+        assert_eq!(char_grid.bounds, bounds);
+        assert_eq!(char_grid.get((1, 1)), Some(&'#'));
+        assert_eq!(char_grid.get((2, 0)), Some(&'.'));
+        assert_eq!(char_grid.get((-1, -1)), Some(&'#'));
+    }
+
+    #[test]
+    fn pad_small_grid() {
+        let grid = VecGrid::with_generator(Rect::with_corners((3, -1), (5, 1)), |(x, y)| x + y);
+        let padded = grid.pad(1, -9);
+        assert_eq!(padded.bounds, Rect::with_corners((2, -2), (6, 2)));
+        assert_eq!(padded.bounds.area(), 16);
+        for (coord, &cell) in padded.iter() {
+            match grid.get(coord) {
+                Some(&original) => assert_eq!(cell, original),
+                None => assert_eq!(cell, -9),
+            }
+        }
+        assert_eq!(padded.get((4, 0)), Some(&4));
+        assert_eq!(grid.pad(0, -9), grid);
+        assert_eq!(grid.pad(-2, -9), grid);
+    }
+
+    #[test]
+    fn count_and_find_live_cells() {
+        let grid = VecGrid::with_generator(Rect::with_corners((-2, -1), (3, 2)), |(x, y)| {
+            x > 0 && y >= 0
+        });
+        assert_eq!(grid.count(|&cell| cell), 4);
+        assert_eq!(grid.count(|&cell| !cell), 11);
+        assert_eq!(grid.find(|&cell| cell), Some(Coord::new(1, 0)));
+        assert_eq!(grid.find(|&cell| !cell), Some(Coord::new(-2, -1)));
+
+        let empty = VecGrid::<bool>::new(Rect::new((3, 3)));
+        assert_eq!(empty.count(|&cell| cell), 0);
+        assert_eq!(empty.find(|&cell| cell), None);
+    }
+
+    #[test]
+    fn zip_map_adds_layers() {
+        let bounds = Rect::with_corners((-1, -1), (2, 1));
+        let a = VecGrid::with_generator(bounds, |(x, _y)| x);
+        let b = VecGrid::with_generator(bounds, |(_x, y)| 10 * y);
+        let sum = a.zip_map(&b, |&a, &b| a + b).unwrap();
+        assert_eq!(sum.bounds, bounds);
+        assert_eq!(sum.get((-1, -1)), Some(&-11));
+        assert_eq!(sum.get((1, 0)), Some(&1));
+        assert_eq!(sum.get((0, 0)), Some(&0));
+    }
+
+    #[test]
+    fn zip_map_mismatched_bounds() {
+        let a = VecGrid::<i32>::new(Rect::new((3, 2)));
+        let b = VecGrid::<i32>::new(Rect::new((2, 3)));
+        assert_eq!(
+            a.zip_map(&b, |&a, &b| a + b).unwrap_err(),
+            GridError::InvalidBounds
+        );
+        let shifted = VecGrid::<i32>::new(Rect::with_corners((1, 0), (4, 2)));
+        assert!(a.zip_map(&shifted, |&a, &b| a + b).is_err());
+    }
+
+    #[test]
+    fn row_and_column_accessors() {
+        let grid =
+            VecGrid::with_generator(Rect::with_corners((-1, 0), (2, 3)), |(x, y)| x * 10 + y);
+        let row = grid.row(Row(1)).unwrap().collect::<Vec<_>>();
+        assert_eq!(row.len(), 3);
+        for (coord, cell) in row {
+            assert_eq!(coord.y, 1);
+            assert_eq!(grid.get(coord), Some(cell));
+        }
+        let column = grid.column(Col(-1)).unwrap().collect::<Vec<_>>();
+        assert_eq!(column.len(), 3);
+        for (coord, cell) in column {
+            assert_eq!(coord.x, -1);
+            assert_eq!(grid.get(coord), Some(cell));
+        }
+        assert!(grid.row(Row(3)).is_none());
+        assert!(grid.column(Col(2)).is_none());
+    }
+
+    #[test]
+    fn untyped_row_index() {
+        let grid = VecGrid::with_generator(Rect::new((3, 2)), |(x, y)| x * 10 + y);
+        let xs = grid
+            .row_at(0)
+            .unwrap()
+            .map(|(coord, _cell)| coord.x)
+            .collect::<Vec<_>>();
+        assert_eq!(xs, vec![0, 1, 2]);
+        let ys = grid
+            .col_at(2)
+            .unwrap()
+            .map(|(coord, _cell)| coord.y)
+            .collect::<Vec<_>>();
+        assert_eq!(ys, vec![0, 1]);
+        assert!(grid.col_at(-1).is_none());
+        assert!(grid.row_at(2).is_none());
+    }
+
+    #[test]
+    fn rows_iterator() {
+        let grid =
+            VecGrid::with_generator(Rect::with_corners((0, -1), (3, 1)), |(x, y)| x * 10 + y);
+        let rows = grid
+            .rows()
+            .map(|row| row.map(|(_coord, &cell)| cell).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec![-1, 9, 19], vec![0, 10, 20]]);
+    }
+
+    #[test]
+    fn antidiagonal_group_sizes() {
+        let grid = VecGrid::<()>::new(Rect::new((3, 3)));
+        let sizes = grid
+            .iter_antidiagonals()
+            .map(|cells| cells.len())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![1, 2, 3, 2, 1]);
+    }
+
+    #[test]
+    fn antidiagonal_sums() {
+        let grid = VecGrid::<()>::new(Rect::with_corners((-1, 2), (3, 4)));
+        for (sum, cells) in (1..).zip(grid.iter_antidiagonals()) {
+            assert!(cells.iter().all(|(coord, _cell)| coord.x + coord.y == sum));
+        }
+        assert_eq!(grid.iter_antidiagonals().flatten().count(), 8);
+    }
+
+    #[test]
+    fn rows_slice_length() {
+        let grid = VecGrid::with_generator(Rect::with_corners((0, -2), (5, 4)), |(_x, y)| y);
+        let slice = grid.rows_slice(-1, 2).unwrap();
+        assert_eq!(slice.len(), (grid.bounds.width() * 3) as usize);
+        assert_eq!(slice.first(), Some(&-1));
+        assert_eq!(slice.last(), Some(&1));
+    }
+
+    #[test]
+    fn rows_slice_out_of_bounds() {
+        let grid = VecGrid::<()>::new(Rect::new((4, 4)));
+        assert_eq!(grid.rows_slice(-1, 2), None);
+        assert_eq!(grid.rows_slice(2, 5), None);
+        assert_eq!(grid.rows_slice(3, 2), None);
+    }
+
+    #[test]
+    fn boundary_fill_inside_ring() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((7, 7)));
+        for coord in Rect::with_corners((1, 1), (6, 6)).iter() {
+            if coord.x == 1 || coord.x == 5 || coord.y == 1 || coord.y == 5 {
+                grid.set(coord, 1);
+            }
+        }
+        // The interior contains other values, which are filled over.
+        grid.set((3, 3), 5);
+        assert_eq!(grid.boundary_fill((2, 2), &1, 2), 9);
+        assert_eq!(grid.get((3, 3)), Some(&2));
+        assert_eq!(grid.get((0, 0)), Some(&0));
+        assert_eq!(grid.get((6, 3)), Some(&0));
+    }
+
+    #[test]
+    fn boundary_fill_from_boundary() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((3, 3)));
+        assert_eq!(grid.boundary_fill((1, 1), &0, 2), 0);
+    }
+
+    #[test]
+    fn remap_tiles_to_chars() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum Tile {
+            Floor,
+            Wall,
+            Water,
+        }
+
+        let grid = VecGrid::from_parts(
+            Rect::new((3, 1)),
+            vec![Tile::Floor, Tile::Wall, Tile::Water],
+        )
+        .unwrap();
+        let table = [(Tile::Floor, '.'), (Tile::Wall, '#')]
+            .iter()
+            .copied()
+            .collect::<HashMap<_, _>>();
+        let chars = grid.remap(&table, '?');
+        assert_eq!(chars.bounds, grid.bounds);
+        assert_eq!(chars.cells, vec!['.', '#', '?']);
+    }
+
+    #[test]
+    fn diagonal_flood_crosses_diagonal_gap() {
+        // Two open cells joined only at their corners.
+        let grid = VecGrid::with_generator(Rect::new((2, 2)), |(x, y)| x == y);
+        assert_eq!(grid.flood_iter((0, 0), |&open| open).count(), 1);
+        let options = FloodOptions {
+            diagonal: true,
+            ..FloodOptions::default()
+        };
+        let coords = grid
+            .flood_iter_with((0, 0), |&open| open, options)
+            .map(|(coord, _cell)| coord)
+            .collect::<Vec<_>>();
+        assert_eq!(coords, vec![Coord::new(0, 0), Coord::new(1, 1)]);
+    }
+
+    #[test]
+    fn flood_max_distance() {
+        let grid = VecGrid::<()>::new(Rect::new((5, 5)));
+        let options = FloodOptions {
+            max_distance: Some(1),
+            ..FloodOptions::default()
+        };
+        let coords = grid
+            .flood_iter_with((2, 2), |_| true, options)
+            .map(|(coord, _cell)| coord)
+            .collect::<HashSet<_>>();
+        let mut expected = Neighborhood::new((2, 2))
+            .iter_ortho()
+            .collect::<HashSet<_>>();
+        expected.insert(Coord::new(2, 2));
+        assert_eq!(coords, expected);
+    }
+
+    #[test]
+    fn crop_center() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x + y * 4);
+        let cropped = grid.crop(Rect::with_corners((1, 1), (3, 3)));
+        assert_eq!(cropped.bounds, Rect::with_corners((1, 1), (3, 3)));
+        assert_eq!(cropped.cells, vec![5, 6, 9, 10]);
+        for (coord, cell) in cropped.iter() {
+            assert_eq!(grid.get(coord), Some(cell));
+        }
+    }
+
+    #[test]
+    fn crop_outside_bounds() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x + y * 4);
+        let clipped = grid.crop(Rect::with_corners((2, -2), (6, 1)));
+        assert_eq!(clipped.bounds, Rect::with_corners((2, 0), (4, 1)));
+        assert_eq!(clipped.cells, vec![2, 3]);
+        let empty = grid.crop(Rect::with_corners((5, 5), (7, 7)));
+        assert_eq!(empty.bounds.area(), 0);
+        assert!(empty.cells.is_empty());
+    }
+
+    #[test]
+    fn corner_content_bounds() {
+        let mut grid = VecGrid::<u8>::new(Rect::with_corners((-2, -2), (6, 6)));
+        assert_eq!(grid.content_bounds(), None);
+        grid.set((5, 3), 1);
+        grid.set((3, 5), 2);
+        grid.set((4, 4), 3);
+        assert_eq!(
+            grid.content_bounds(),
+            Some(Rect::with_corners((3, 3), (6, 6)))
+        );
+    }
+
+    #[test]
+    fn wrapping_access() {
+        let mut grid = VecGrid::with_generator(Rect::new((3, 2)), |(x, y)| x + y * 10);
+        assert_eq!(grid.get_wrapping((-1, 1)), &12);
+        assert_eq!(grid.get_wrapping((3, 1)), &10);
+        assert_eq!(grid.get_wrapping((-4, 5)), &12);
+        *grid.get_wrapping_mut((0, -1)) = 99;
+        assert_eq!(grid.get((0, 1)), Some(&99));
+    }
+
+    #[test]
+    fn wrap_offset_coords() {
+        let grid = VecGrid::<()>::new(Rect::with_corners((-2, 3), (2, 5)));
+        assert_eq!(grid.wrap_coord((-3, 3)), Coord::new(1, 3));
+        assert_eq!(grid.wrap_coord((2, 5)), Coord::new(-2, 3));
+        assert_eq!(grid.wrap_coord((0, 4)), Coord::new(0, 4));
+    }
+
+    #[test]
+    fn disjoint_mut_neighbors() {
+        let mut grid = VecGrid::with_generator(Rect::new((3, 3)), |(x, y)| x + y * 3);
+        let neighbors = [Coord::NORTH, Coord::EAST, Coord::SOUTH, Coord::WEST]
+            .map(|offset| Coord::ONE + offset);
+        let [north, east, south, west] = grid.get_disjoint_mut(neighbors).unwrap();
+        std::mem::swap(north, south);
+        std::mem::swap(east, west);
+        assert_eq!(grid.cells, vec![0, 7, 2, 5, 4, 3, 6, 1, 8]);
+    }
+
+    #[test]
+    fn invalid_disjoint_mut_coords() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((3, 3)));
+        assert_eq!(
+            grid.get_disjoint_mut([Coord::ZERO, Coord::ONE, Coord::ZERO]),
+            Err(GridError::AlreadyVisited(Coord::ZERO))
+        );
+        assert_eq!(
+            grid.get_disjoint_mut([Coord::ONE, Coord::new(3, 0)]),
+            Err(GridError::OutOfBounds(Coord::new(3, 0)))
+        );
+    }
+
+    #[test]
+    fn mov_to_empty_cell() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((2, 2)));
+        grid.set((0, 0), 5);
+        assert_eq!(grid.mov(Coord::new(0, 0), Coord::new(1, 1)), Some(0));
+        assert_eq!(grid.cells, vec![0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn mov_overwrites_cell() {
+        let mut grid = VecGrid::from_parts(Rect::new((2, 1)), vec![3, 4]).unwrap();
+        assert_eq!(grid.mov(Coord::new(1, 0), Coord::new(0, 0)), Some(3));
+        assert_eq!(grid.cells, vec![4, 0]);
+        assert_eq!(grid.mov(Coord::new(1, 0), Coord::new(2, 0)), None);
+        assert_eq!(grid.cells, vec![4, 0]);
+    }
+
+    #[test]
+    fn mov_onto_itself() {
+        let mut grid = VecGrid::from_parts(Rect::new((2, 1)), vec![3, 4]).unwrap();
+        assert_eq!(grid.mov(Coord::new(1, 0), Coord::new(1, 0)), Some(4));
+        assert_eq!(grid.cells, vec![3, 4]);
+    }
+
+    #[test]
+    fn morton_order() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x + y * 4);
+        let order = grid
+            .iter_morton()
+            .map(|(_coord, &cell)| cell)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![0, 1, 4, 5, 2, 3, 6, 7, 8, 9, 12, 13, 10, 11, 14, 15]
+        );
+    }
+
+    #[test]
+    fn morton_order_skips_out_of_bounds() {
+        let grid = VecGrid::<()>::new(Rect::with_corners((-1, 2), (2, 4)));
+        let coords = grid
+            .iter_morton()
+            .map(|(coord, _cell)| coord)
+            .collect::<Vec<_>>();
+        let expected = [(-1, 2), (0, 2), (-1, 3), (0, 3), (1, 2), (1, 3)]
+            .iter()
+            .map(|&coord| Coord::from(coord))
+            .collect::<Vec<_>>();
+        assert_eq!(coords, expected);
+    }
+
+    #[test]
+    fn try_copy_cells() {
+        let mut grid = VecGrid::from_parts(Rect::new((2, 1)), vec![3, 4]).unwrap();
+        assert_eq!(grid.try_copy((1, 0), (0, 0)), Ok(()));
+        assert_eq!(grid.get((0, 0)), grid.get((1, 0)));
+        assert_eq!(grid.cells, vec![4, 4]);
+    }
+
+    #[test]
+    fn try_copy_out_of_bounds() {
+        let mut grid = VecGrid::from_parts(Rect::new((2, 1)), vec![3, 4]).unwrap();
+        assert_eq!(
+            grid.try_copy((-1, 0), (0, 0)),
+            Err(GridError::SourceOutOfBounds(Coord::new(-1, 0)))
+        );
+        assert_eq!(
+            grid.try_copy((0, 0), (0, 1)),
+            Err(GridError::DestOutOfBounds(Coord::new(0, 1)))
+        );
+        assert_eq!(
+            grid.try_copy((2, 0), (2, 0)),
+            Err(GridError::SourceOutOfBounds(Coord::new(2, 0)))
+        );
+        assert_eq!(grid.cells, vec![3, 4]);
+        assert!(!grid.copy((0, 0), (0, 1)));
+    }
+
+    #[test]
+    fn copy_onto_itself() {
+        let mut grid = VecGrid::from_parts(Rect::new((2, 1)), vec![3, 4]).unwrap();
+        assert!(!grid.copy((1, 0), (1, 0)));
+        assert_eq!(
+            grid.try_copy((1, 0), (1, 0)),
+            Err(GridError::SameCoord(Coord::new(1, 0)))
+        );
+        assert_eq!(grid.cells, vec![3, 4]);
+    }
+
+    #[test]
+    fn grow_grid() {
+        let mut grid = VecGrid::with_generator(Rect::new((2, 2)), |(x, y)| x + y * 2 + 1);
+        grid.resize(Rect::with_corners((-1, 0), (3, 3)));
+        assert_eq!(grid.bounds, Rect::with_corners((-1, 0), (3, 3)));
+        assert_eq!(grid.cells, vec![0, 1, 2, 0, 0, 3, 4, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn shrink_grid() {
+        let mut grid = VecGrid::with_generator(Rect::new((3, 3)), |(x, y)| x + y * 3);
+        grid.resize(Rect::with_corners((1, 1), (3, 2)));
+        assert_eq!(grid.cells, vec![4, 5]);
+        grid.resize(Rect::with_corners((10, 10), (11, 11)));
+        assert_eq!(grid.cells, vec![0]);
+    }
+
+    #[test]
+    fn overlay_inside() {
+        let mut grid = VecGrid::new(Rect::new((4, 3)));
+        grid.fill('.');
+        let stamp = VecGrid::from_parts(Rect::new((2, 2)), vec!['#', ' ', '#', '#']).unwrap();
+        // Treat spaces as transparent.
+        grid.overlay(&stamp, Coord::new(1, 1), |cell, &stamp_cell| {
+            if stamp_cell != ' ' {
+                *cell = stamp_cell;
+            }
+        });
+        assert_eq!(grid.cells, ".....#...##.".chars().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn overlay_clipped_at_edge() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((3, 3)));
+        let stamp = VecGrid::from_parts(Rect::new((2, 2)), vec![1, 2, 3, 4]).unwrap();
+        grid.overlay(&stamp, Coord::new(2, -1), |cell, &stamp_cell| {
+            *cell = stamp_cell
+        });
+        assert_eq!(grid.cells, vec![0, 0, 3, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn overlay_outside_is_noop() {
+        let mut grid = VecGrid::<u8>::new(Rect::new((3, 3)));
+        let stamp = VecGrid::from_parts(Rect::new((2, 2)), vec![1, 2, 3, 4]).unwrap();
+        grid.overlay(&stamp, Coord::new(-5, 5), |cell, &stamp_cell| {
+            *cell = stamp_cell
+        });
+        assert_eq!(grid, VecGrid::new(Rect::new((3, 3))));
+    }
+
+    #[test]
+    fn random_placement_fits() {
+        let grid = VecGrid::<()>::new(Rect::with_corners((-4, -4), (6, 4)));
+        let size = Coord::new(3, 5);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let position = grid.random_placement(size, &mut rng).unwrap();
+            assert!(grid.bounds.contains(position));
+            assert!(grid.bounds.contains(position + size - Coord::ONE));
+        }
+    }
+
+    #[test]
+    fn random_placement_too_large() {
+        let grid = VecGrid::<()>::new(Rect::new((4, 4)));
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(grid.random_placement(Coord::new(5, 1), &mut rng), None);
+    }
+
     #[test]
     fn selection_iter_mut() {
         let mut grid: VecGrid<bool> = VecGrid::new(Rect::new((4, 4)));
@@ -382,4 +1515,37 @@ mod tests {
         assert!(iter.next().unwrap().is_ok());
         assert!(iter.next().unwrap() == Err(GridError::AlreadyVisited(Coord::new(2, 2))));
     }
+
+    #[test]
+    fn selection_iter_mut_revisits_in_any_order() {
+        let mut grid = VecGrid::from_parts(Rect::new((4, 1)), vec![0, 1, 2, 3]).unwrap();
+        let coords = [(2, 0), (0, 0), (2, 0), (3, 0), (1, 0), (0, 0)];
+        let visited = grid
+            .selection_iter_mut(coords.iter().map(|&x| x.into()))
+            .map(|res_cell| res_cell.map(|(_coord, cell)| *cell))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            visited,
+            vec![
+                Ok(2),
+                Ok(0),
+                Err(GridError::AlreadyVisited(Coord::new(2, 0))),
+                Ok(3),
+                Ok(1),
+                Err(GridError::AlreadyVisited(Coord::new(0, 0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_iter_mut_holds_cells_at_once() {
+        let mut grid = VecGrid::from_parts(Rect::new((3, 1)), vec![1, 2, 3]).unwrap();
+        let coords = [(0, 0), (5, 0), (2, 0)].iter().map(|&x| x.into());
+        let cells = grid.selection_iter_mut(coords).collect::<Vec<_>>();
+        assert_eq!(cells[1], Err(GridError::OutOfBounds(Coord::new(5, 0))));
+        let mut cells = cells.into_iter().flatten().collect::<Vec<_>>();
+        let (first, last) = cells.split_at_mut(1);
+        std::mem::swap(first[0].1, last[0].1);
+        assert_eq!(grid.cells, vec![3, 2, 1]);
+    }
 }
@@ -0,0 +1,366 @@
+use crate::{
+    coord::Coord,
+    grid::{Grid, GridError},
+    patterns::{Neighborhood, Rect},
+    vecgrid::VecGrid,
+};
+
+impl<T> VecGrid<T> {
+    /// Advances a cellular automaton by one generation, returning a new grid
+    /// where each cell is `rule(cell, neighbors)`.
+    ///
+    /// `neighbors` holds the cell's in-bounds Moore neighbors, so cells on the
+    /// edge have fewer of them. All cells are computed from the state of the
+    /// grid before the step.
+    pub fn step<F>(&self, rule: F) -> VecGrid<T>
+    where
+        T: Clone,
+        F: Fn(&T, &[&T]) -> T,
+    {
+        let mut neighbors = Vec::with_capacity(8);
+        let cells = self
+            .iter()
+            .map(|(coord, cell)| {
+                neighbors.clear();
+                neighbors.extend(
+                    Neighborhood::new(coord)
+                        .iter()
+                        .filter_map(|neighbor| self.get(neighbor)),
+                );
+                rule(cell, &neighbors)
+            })
+            .collect();
+        VecGrid {
+            cells,
+            bounds: self.bounds,
+        }
+    }
+}
+
+impl VecGrid<bool> {
+    /// Parses a pattern in the run-length encoded (`.rle`) format commonly
+    /// used to share Game of Life patterns, with live cells as `true`.
+    ///
+    /// The first row of the pattern is placed at `y = 0`. Comment lines
+    /// (starting with `#`) are skipped and the `rule` in the header is ignored.
+    /// Returns [`GridError::InvalidFormat`](GridError::InvalidFormat) for a
+    /// malformed header or body, including dimensions or run counts too large
+    /// to represent, and
+    /// [`GridError::InvalidBounds`](GridError::InvalidBounds) if the body
+    /// doesn't fit within the dimensions given in the header.
+    pub fn from_life_rle(s: &str) -> Result<VecGrid<bool>, GridError> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let header = lines.next().ok_or(GridError::InvalidFormat)?;
+        let mut width = None;
+        let mut height = None;
+        for entry in header.split(',') {
+            let (key, value) = entry.split_once('=').ok_or(GridError::InvalidFormat)?;
+            let dimension = match key.trim() {
+                "x" => &mut width,
+                "y" => &mut height,
+                _ => continue,
+            };
+            let value = value
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| GridError::InvalidFormat)?;
+            if value < 0 {
+                return Err(GridError::InvalidFormat);
+            }
+            *dimension = Some(value);
+        }
+        let dimensions = match (width, height) {
+            (Some(width), Some(height)) => Coord::new(width, height),
+            _ => return Err(GridError::InvalidFormat),
+        };
+        if dimensions.x.checked_mul(dimensions.y).is_none() {
+            return Err(GridError::InvalidFormat);
+        }
+
+        let mut grid = VecGrid::new(Rect::new(dimensions));
+        let mut cursor = Coord::ZERO;
+        let mut run_count: i32 = 0;
+        for tag in lines.flat_map(str::chars) {
+            if let Some(digit) = tag.to_digit(10) {
+                run_count = run_count
+                    .checked_mul(10)
+                    .and_then(|count| count.checked_add(digit as i32))
+                    .ok_or(GridError::InvalidFormat)?;
+                continue;
+            }
+            // A tag without a preceding count is a run of one.
+            let run_length = run_count.max(1);
+            run_count = 0;
+            match tag {
+                'b' => {
+                    cursor.x = cursor
+                        .x
+                        .checked_add(run_length)
+                        .ok_or(GridError::InvalidFormat)?;
+                }
+                'o' => {
+                    for _ in 0..run_length {
+                        if !grid.set(cursor, true) {
+                            return Err(GridError::InvalidBounds);
+                        }
+                        cursor.x += 1;
+                    }
+                }
+                '$' => {
+                    cursor.x = 0;
+                    cursor.y = cursor
+                        .y
+                        .checked_add(run_length)
+                        .ok_or(GridError::InvalidFormat)?;
+                }
+                '!' => break,
+                tag if tag.is_whitespace() => {}
+                _ => return Err(GridError::InvalidFormat),
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Removes single-cell noise by flipping every cell that has fewer than
+    /// `min_neighbors` Moore neighbors sharing its value.
+    ///
+    /// In other words, `true` cells with fewer than `min_neighbors` `true`
+    /// neighbors are cleared, and `false` cells with fewer than
+    /// `min_neighbors` `false` neighbors are filled. Only in-bounds neighbors
+    /// are counted, so cells on the edges of the grid, which have fewer
+    /// neighbors, flip more easily. All cells are updated simultaneously,
+    /// based on the state of the grid before the call.
+    pub fn despeckle(&mut self, min_neighbors: u8) {
+        let next_cells = self
+            .iter()
+            .map(|(coord, &cell)| {
+                let like_neighbors = Neighborhood::new(coord)
+                    .iter()
+                    .filter(|&neighbor| self.get(neighbor) == Some(&cell))
+                    .count();
+                if like_neighbors < min_neighbors as usize {
+                    !cell
+                } else {
+                    cell
+                }
+            })
+            .collect();
+        self.cells = next_cells;
+    }
+
+    /// Compares each cell with the same cell in the `next` generation,
+    /// returning 1 where a cell was born (`false` to `true`), -1 where it died
+    /// (`true` to `false`) and 0 where it's unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grids don't have identical bounds.
+    pub fn transitions(&self, next: &VecGrid<bool>) -> VecGrid<i8> {
+        self.zip_map(next, |&before, &after| after as i8 - before as i8)
+            .expect("transitions requires grids with identical bounds")
+    }
+
+    /// Returns the coords of the cells equal to `passable_value` that have
+    /// exactly one orthogonal neighbor that is also passable, such as the dead
+    /// ends of a maze, in row-major order.
+    pub fn dead_ends(&self, passable_value: bool) -> Vec<Coord> {
+        self.iter()
+            .filter(|&(coord, &cell)| {
+                cell == passable_value
+                    && self
+                        .neighbors_ortho(coord)
+                        .filter(|&(_neighbor, &neighbor_cell)| neighbor_cell == passable_value)
+                        .count()
+                        == 1
+            })
+            .map(|(coord, _cell)| coord)
+            .collect()
+    }
+
+    /// Fills in the [`dead_ends`](Self::dead_ends) of a maze up to
+    /// `iterations` times, shortening each dead end corridor by one cell per
+    /// iteration. Stops early once no dead ends remain.
+    ///
+    /// Corridors that lead nowhere are removed entirely given enough
+    /// iterations, leaving only the loops between them.
+    pub fn fill_dead_ends(&mut self, passable_value: bool, iterations: usize) {
+        for _ in 0..iterations {
+            let dead_ends = self.dead_ends(passable_value);
+            if dead_ends.is_empty() {
+                break;
+            }
+            for coord in dead_ends {
+                self.set(coord, !passable_value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life_rule(&cell: &bool, neighbors: &[&bool]) -> bool {
+        let live_neighbors = neighbors.iter().filter(|&&&neighbor| neighbor).count();
+        live_neighbors == 3 || (cell && live_neighbors == 2)
+    }
+
+    #[test]
+    fn step_blinker_oscillates() {
+        let horizontal =
+            VecGrid::with_generator(Rect::new((5, 5)), |(x, y)| y == 2 && (1..4).contains(&x));
+        let vertical =
+            VecGrid::with_generator(Rect::new((5, 5)), |(x, y)| x == 2 && (1..4).contains(&y));
+        let next = horizontal.step(life_rule);
+        assert_eq!(next, vertical);
+        assert_eq!(next.step(life_rule), horizontal);
+    }
+
+    #[test]
+    fn blinker_transitions() {
+        let horizontal =
+            VecGrid::with_generator(Rect::new((5, 5)), |(x, y)| y == 2 && (1..4).contains(&x));
+        let transitions = horizontal.transitions(&horizontal.step(life_rule));
+        assert_eq!(transitions.get((2, 1)), Some(&1));
+        assert_eq!(transitions.get((2, 3)), Some(&1));
+        assert_eq!(transitions.get((1, 2)), Some(&-1));
+        assert_eq!(transitions.get((3, 2)), Some(&-1));
+        assert_eq!(transitions.get((2, 2)), Some(&0));
+        assert_eq!(
+            transitions
+                .iter()
+                .filter(|(_coord, &change)| change != 0)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn transitions_with_mismatched_bounds() {
+        let grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        grid.transitions(&VecGrid::new(Rect::new((4, 5))));
+    }
+
+    #[test]
+    fn step_omits_out_of_bounds_neighbors() {
+        let grid = VecGrid::<u8>::new(Rect::with_corners((-1, -1), (2, 2)));
+        let counts = grid.step(|_cell, neighbors| neighbors.len() as u8);
+        assert_eq!(counts.get((-1, -1)), Some(&3));
+        assert_eq!(counts.get((0, -1)), Some(&5));
+        assert_eq!(counts.get((0, 0)), Some(&8));
+    }
+
+    #[test]
+    fn parse_glider_rle() {
+        let rle = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+        let grid = VecGrid::from_life_rle(rle).unwrap();
+        assert_eq!(grid.bounds, Rect::new((3, 3)));
+        let live_cells = grid
+            .iter()
+            .filter(|(_coord, &cell)| cell)
+            .map(|(coord, _cell)| coord)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            live_cells,
+            vec![
+                Coord::new(1, 0),
+                Coord::new(2, 1),
+                Coord::new(0, 2),
+                Coord::new(1, 2),
+                Coord::new(2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_rle() {
+        assert_eq!(VecGrid::from_life_rle(""), Err(GridError::InvalidFormat));
+        assert_eq!(
+            VecGrid::from_life_rle("x = 3\nooo!"),
+            Err(GridError::InvalidFormat)
+        );
+        assert_eq!(
+            VecGrid::from_life_rle("x = 2, y = 1\nooo!"),
+            Err(GridError::InvalidBounds)
+        );
+    }
+
+    #[test]
+    fn parse_overflowing_rle() {
+        assert_eq!(
+            VecGrid::from_life_rle("x = 3, y = 1\n99999999999o!"),
+            Err(GridError::InvalidFormat)
+        );
+        assert_eq!(
+            VecGrid::from_life_rle("x = 3, y = 1\n2000000000b2000000000b!"),
+            Err(GridError::InvalidFormat)
+        );
+        assert_eq!(
+            VecGrid::from_life_rle("x = 3, y = 1\n2000000000$2000000000$!"),
+            Err(GridError::InvalidFormat)
+        );
+        assert_eq!(
+            VecGrid::from_life_rle("x = 65536, y = 65536\no!"),
+            Err(GridError::InvalidFormat)
+        );
+    }
+
+    fn maze(art: &str) -> VecGrid<bool> {
+        VecGrid::from_ascii(art, '#')
+            .unwrap()
+            .map(|&cell| cell == '.')
+    }
+
+    #[test]
+    fn find_maze_dead_ends() {
+        let mut grid = maze(".#...\n.#.#.\n...#.\n####.");
+        assert_eq!(
+            grid.dead_ends(true),
+            vec![Coord::new(0, 0), Coord::new(4, 3)]
+        );
+        grid.fill_dead_ends(true, 1);
+        assert_eq!(
+            grid.dead_ends(true),
+            vec![Coord::new(0, 1), Coord::new(4, 2)]
+        );
+
+        let walls = grid.map(|&cell| !cell);
+        assert_eq!(walls.dead_ends(false), grid.dead_ends(true));
+    }
+
+    #[test]
+    fn fill_dead_ends_keeps_loops() {
+        let mut grid = maze("....\n.#.#\n...#");
+        assert_eq!(grid.dead_ends(true), vec![Coord::new(3, 0)]);
+        grid.fill_dead_ends(true, 10);
+        assert!(grid.dead_ends(true).is_empty());
+        assert_eq!(grid.iter().filter(|(_coord, &cell)| cell).count(), 8);
+        assert_eq!(grid.get((3, 0)), Some(&false));
+    }
+
+    #[test]
+    fn despeckle_isolated_cells() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((5, 5)));
+        grid.set((2, 2), true);
+        grid.despeckle(2);
+        assert!(grid.iter().all(|(_coord, &cell)| !cell));
+
+        let mut grid = VecGrid::with_generator(Rect::new((5, 5)), |_: Coord| true);
+        grid.set((2, 2), false);
+        grid.despeckle(2);
+        assert!(grid.iter().all(|(_coord, &cell)| cell));
+    }
+
+    #[test]
+    fn despeckle_keeps_solid_regions() {
+        let mut grid = VecGrid::with_generator(Rect::new((6, 6)), |(x, _y)| x < 3);
+        let original = grid.clone();
+        grid.despeckle(3);
+        assert_eq!(grid, original);
+    }
+}
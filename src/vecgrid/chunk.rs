@@ -0,0 +1,231 @@
+use crate::{
+    coord::Coord,
+    grid::{Grid, GridError},
+    patterns::Rect,
+    vecgrid::VecGrid,
+};
+
+impl<T> VecGrid<T> {
+    /// Splits the grid into sub-grids of `chunk_size` which tile the original,
+    /// each keeping the bounds of the area it was copied from. Chunks along
+    /// the right and bottom edges are smaller if the grid dimensions aren't a
+    /// multiple of `chunk_size`.
+    ///
+    /// Chunks are returned in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension of `chunk_size` is not positive.
+    pub fn into_chunks(&self, chunk_size: Coord) -> Vec<VecGrid<T>>
+    where
+        T: Clone,
+    {
+        assert!(
+            chunk_size.x > 0 && chunk_size.y > 0,
+            "chunk dimensions must be positive"
+        );
+        let mut chunks = vec![];
+        for top in self.bounds.y_range().step_by(chunk_size.y as usize) {
+            for left in self.bounds.x_range().step_by(chunk_size.x as usize) {
+                let chunk_bounds = Rect::with_corners(
+                    (left, top),
+                    (
+                        (left + chunk_size.x).min(self.bounds.right),
+                        (top + chunk_size.y).min(self.bounds.bottom),
+                    ),
+                );
+                chunks.push(VecGrid::with_generator(chunk_bounds, |coord: Coord| {
+                    self.get(coord).unwrap().clone()
+                }));
+            }
+        }
+        chunks
+    }
+
+    /// Stitches `chunks` back into a single grid covering their combined
+    /// bounds, placing each chunk's cells at their absolute coords. This is
+    /// the inverse of [`into_chunks`](Self::into_chunks).
+    ///
+    /// Returns [`GridError::InvalidBounds`](GridError::InvalidBounds) if there
+    /// are no chunks, or if the chunks overlap or leave any gaps.
+    pub fn from_chunks(chunks: &[VecGrid<T>]) -> Result<VecGrid<T>, GridError>
+    where
+        T: Clone,
+    {
+        let bounds = chunks
+            .iter()
+            .map(|chunk| chunk.bounds)
+            .reduce(|a, b| Rect {
+                top: a.top.min(b.top),
+                bottom: a.bottom.max(b.bottom),
+                left: a.left.min(b.left),
+                right: a.right.max(b.right),
+            })
+            .ok_or(GridError::InvalidBounds)?;
+
+        let mut stitched = VecGrid::<Option<T>>::new(bounds);
+        for chunk in chunks.iter() {
+            for (coord, cell) in chunk.iter() {
+                let stitched_cell = stitched.get_mut(coord).unwrap();
+                if stitched_cell.is_some() {
+                    return Err(GridError::InvalidBounds);
+                }
+                *stitched_cell = Some(cell.clone());
+            }
+        }
+        let cells = stitched
+            .cells
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or(GridError::InvalidBounds)?;
+        Ok(VecGrid { cells, bounds })
+    }
+
+    /// Joins `other` onto the right side of `self`, returning a grid with the
+    /// bounds of `self` widened by the width of `other`.
+    ///
+    /// Returns [`GridError::InvalidBounds`](GridError::InvalidBounds) if the
+    /// grids have different heights.
+    pub fn concat_horizontal(&self, other: &VecGrid<T>) -> Result<VecGrid<T>, GridError>
+    where
+        T: Clone,
+    {
+        if self.bounds.height() != other.bounds.height() {
+            return Err(GridError::InvalidBounds);
+        }
+        let bounds = Rect {
+            right: self.bounds.right + other.bounds.width(),
+            ..self.bounds
+        };
+        let (width, other_width) = (self.bounds.width() as usize, other.bounds.width() as usize);
+        let mut cells = Vec::with_capacity(bounds.area() as usize);
+        for row in 0..self.bounds.height() as usize {
+            cells.extend_from_slice(&self.cells[row * width..(row + 1) * width]);
+            cells.extend_from_slice(&other.cells[row * other_width..(row + 1) * other_width]);
+        }
+        Ok(VecGrid { cells, bounds })
+    }
+
+    /// Joins `other` onto the bottom of `self`, returning a grid with the
+    /// bounds of `self` lengthened by the height of `other`.
+    ///
+    /// Returns [`GridError::InvalidBounds`](GridError::InvalidBounds) if the
+    /// grids have different widths.
+    pub fn concat_vertical(&self, other: &VecGrid<T>) -> Result<VecGrid<T>, GridError>
+    where
+        T: Clone,
+    {
+        if self.bounds.width() != other.bounds.width() {
+            return Err(GridError::InvalidBounds);
+        }
+        let bounds = Rect {
+            bottom: self.bounds.bottom + other.bounds.height(),
+            ..self.bounds
+        };
+        let cells = self
+            .cells
+            .iter()
+            .chain(other.cells.iter())
+            .cloned()
+            .collect();
+        Ok(VecGrid { cells, bounds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_into_even_chunks() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x + y * 4);
+        let chunks = grid.into_chunks(Coord::new(2, 2));
+        let offsets = chunks
+            .iter()
+            .map(|chunk| chunk.bounds.offset())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            vec![
+                Coord::new(0, 0),
+                Coord::new(2, 0),
+                Coord::new(0, 2),
+                Coord::new(2, 2),
+            ]
+        );
+        for chunk in chunks.iter() {
+            assert_eq!(chunk.bounds.dimensions(), Coord::new(2, 2));
+            assert!(chunk
+                .iter()
+                .all(|(coord, cell)| grid.get(coord) == Some(cell)));
+        }
+    }
+
+    #[test]
+    fn split_into_uneven_chunks() {
+        let grid = VecGrid::<u8>::new(Rect::with_corners((-1, -1), (4, 2)));
+        let chunks = grid.into_chunks(Coord::new(2, 2));
+        assert_eq!(chunks.len(), 6);
+        assert_eq!(
+            chunks.last().unwrap().bounds,
+            Rect::with_corners((3, 1), (4, 2))
+        );
+        let total_area: i32 = chunks.iter().map(|chunk| chunk.bounds.area()).sum();
+        assert_eq!(total_area, grid.bounds.area());
+    }
+
+    #[test]
+    fn concat_grids_horizontally() {
+        let left = VecGrid::from_parts(Rect::new((2, 2)), vec![1, 2, 3, 4]).unwrap();
+        let right =
+            VecGrid::from_parts(Rect::with_corners((5, 5), (7, 7)), vec![5, 6, 7, 8]).unwrap();
+        let joined = left.concat_horizontal(&right).unwrap();
+        assert_eq!(joined.bounds, Rect::new((4, 2)));
+        assert_eq!(joined.cells, vec![1, 2, 5, 6, 3, 4, 7, 8]);
+        assert_eq!(joined.get((2, 1)), Some(&7));
+    }
+
+    #[test]
+    fn concat_grids_vertically() {
+        let top = VecGrid::from_parts(Rect::new((2, 1)), vec![1, 2]).unwrap();
+        let bottom = VecGrid::from_parts(Rect::new((2, 2)), vec![3, 4, 5, 6]).unwrap();
+        let joined = top.concat_vertical(&bottom).unwrap();
+        assert_eq!(joined.bounds, Rect::new((2, 3)));
+        assert_eq!(joined.cells, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn concat_mismatched_grids() {
+        let a = VecGrid::<u8>::new(Rect::new((2, 2)));
+        let b = VecGrid::<u8>::new(Rect::new((2, 3)));
+        assert_eq!(a.concat_horizontal(&b), Err(GridError::InvalidBounds));
+        assert_eq!(
+            a.transpose().concat_vertical(&b.transpose()),
+            Err(GridError::InvalidBounds)
+        );
+        assert!(a.concat_vertical(&b).is_ok());
+    }
+
+    #[test]
+    fn chunk_round_trip() {
+        let grid = VecGrid::with_generator(Rect::with_corners((-3, -2), (4, 5)), |(x, y)| x * y);
+        let chunks = grid.into_chunks(Coord::new(3, 2));
+        assert_eq!(VecGrid::from_chunks(&chunks), Ok(grid));
+    }
+
+    #[test]
+    fn invalid_chunks() {
+        let grid = VecGrid::<u8>::new(Rect::new((4, 4)));
+        let mut chunks = grid.into_chunks(Coord::new(2, 2));
+        assert_eq!(
+            VecGrid::<u8>::from_chunks(&[]),
+            Err(GridError::InvalidBounds)
+        );
+        // Overlapping the first chunk.
+        chunks.push(VecGrid::new(Rect::new((1, 1))));
+        assert_eq!(VecGrid::from_chunks(&chunks), Err(GridError::InvalidBounds));
+        // Leaving a gap where the last chunk was.
+        chunks.truncate(3);
+        assert_eq!(VecGrid::from_chunks(&chunks), Err(GridError::InvalidBounds));
+    }
+}
@@ -0,0 +1,376 @@
+use rand::Rng;
+
+use crate::{
+    coord::Coord,
+    grid::Grid,
+    patterns::{Circle, Neighborhood},
+    vecgrid::{EdgeMode, VecGrid},
+};
+
+impl<T> VecGrid<T> {
+    /// Returns the coords of all cells that are greater than each of their
+    /// in-bounds Moore neighbors, such as the peaks of a heightmap.
+    ///
+    /// If `strict` is `false`, cells that are merely greater than or equal to
+    /// all of their neighbors are included as well, so every cell of a flat
+    /// plateau counts as a maximum.
+    pub fn local_maxima(&self, strict: bool) -> Vec<Coord>
+    where
+        T: PartialOrd,
+    {
+        self.iter()
+            .filter(|&(coord, value)| {
+                Neighborhood::new(coord)
+                    .iter()
+                    .filter_map(|neighbor| self.get(neighbor))
+                    .all(|neighbor_value| {
+                        if strict {
+                            value > neighbor_value
+                        } else {
+                            value >= neighbor_value
+                        }
+                    })
+            })
+            .map(|(coord, _value)| coord)
+            .collect()
+    }
+
+    /// Returns a new grid where each cell is the sum of `weight * value` over
+    /// the cells at each `(offset, weight)` of `kernel` from it, using `edge`
+    /// to sample offsets that fall outside of the bounds.
+    ///
+    /// For example, a 3x3 box blur weights the cell and each of its
+    /// [`Neighborhood::iter_moore`] neighbors by `1.0 / 9.0`.
+    pub fn convolve(&self, kernel: &[(Coord, f32)], edge: EdgeMode) -> VecGrid<f32>
+    where
+        T: Into<f32> + Copy,
+    {
+        let bounds = self.bounds;
+        VecGrid::with_generator(bounds, |coord: Coord| {
+            kernel
+                .iter()
+                .map(|&(offset, weight)| {
+                    let sample = coord + offset;
+                    let value = match edge {
+                        _ if bounds.contains(sample) => self.get(sample).copied(),
+                        EdgeMode::Clamp => self
+                            .get((
+                                sample.x.clamp(bounds.left, bounds.right - 1),
+                                sample.y.clamp(bounds.top, bounds.bottom - 1),
+                            ))
+                            .copied(),
+                        EdgeMode::Wrap => Some(*self.get_wrapping(sample)),
+                        EdgeMode::Zero => None,
+                    };
+                    value.map_or(0.0, |value| weight * value.into())
+                })
+                .sum()
+        })
+    }
+}
+
+impl VecGrid<bool> {
+    /// Blurs the grid into a soft mask, where each cell is the weighted
+    /// fraction of in-bounds cells within `radius` (by Euclidean distance) of
+    /// it that are `true`.
+    ///
+    /// The weight falls off linearly with distance, from `radius + 1` at the
+    /// cell itself to 1 at the edge of the radius, so nearby cells count for
+    /// more.
+    pub fn blur_to_float(&self, radius: i32) -> VecGrid<f32> {
+        let radius = radius.max(0);
+        let offsets = Circle::new(Coord::ZERO, radius)
+            .iter_filled_with_distance()
+            .map(|(offset, distance)| (offset, radius as f32 + 1.0 - distance))
+            .collect::<Vec<_>>();
+        VecGrid::with_generator(self.bounds, |coord: Coord| {
+            let (selected, total) = offsets
+                .iter()
+                .filter_map(|&(offset, weight)| {
+                    self.get(coord + offset).map(|&cell| (cell, weight))
+                })
+                .fold((0.0, 0.0), |(selected, total), (cell, weight)| {
+                    (selected + if cell { weight } else { 0.0 }, total + weight)
+                });
+            selected / total
+        })
+    }
+}
+
+impl VecGrid<f32> {
+    /// Returns the average cell position weighted by cell value, rounded to
+    /// the nearest coord, or `None` if the total weight is zero.
+    ///
+    /// Negative values would let the center escape the grid entirely, so they
+    /// are treated as having zero weight.
+    pub fn center_of_mass(&self) -> Option<Coord> {
+        let (mut total, mut x_sum, mut y_sum) = (0.0, 0.0, 0.0);
+        for (coord, &value) in self.iter() {
+            let weight = value.max(0.0);
+            total += weight;
+            x_sum += weight * coord.x as f32;
+            y_sum += weight * coord.y as f32;
+        }
+        if total <= 0.0 {
+            return None;
+        }
+        Some(Coord::new(
+            (x_sum / total).round() as i32,
+            (y_sum / total).round() as i32,
+        ))
+    }
+
+    /// Whether both grids have the same bounds and every pair of corresponding
+    /// cells differs by at most `epsilon`. NaN cells never compare equal.
+    pub fn approx_eq(&self, other: &VecGrid<f32>, epsilon: f32) -> bool {
+        self.bounds == other.bounds
+            && self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+
+    /// Returns a grid with the same bounds, where each cell holds the offset
+    /// to the Moore neighbor of steepest descent, the direction water would
+    /// flow. Diagonal drops are divided by their longer step length.
+    ///
+    /// Cells with no lower neighbor, such as the bottoms of basins and flat
+    /// areas, hold [`Coord::ZERO`].
+    pub fn gradient(&self) -> VecGrid<Coord> {
+        VecGrid::with_generator(self.bounds, |coord: Coord| self.steepest_descent(coord))
+    }
+
+    /// Runs a simple hydraulic erosion simulation, dropping `droplets` water
+    /// particles at random cells. Each one flows downhill along the
+    /// [`gradient`](Self::gradient), picking up material on steep slopes and
+    /// depositing it where the slope flattens out, until it evaporates or
+    /// settles in a basin.
+    ///
+    /// Over many droplets this carves channels and smooths the terrain.
+    pub fn erode_step(&mut self, droplets: usize, rng: &mut impl Rng) {
+        // How much sediment a droplet can carry per unit of height it drops.
+        const CAPACITY: f32 = 4.0;
+        // The fraction of the remaining capacity picked up per step.
+        const EROSION_RATE: f32 = 0.3;
+        // The fraction of excess sediment dropped per step.
+        const DEPOSITION_RATE: f32 = 0.3;
+        // The fraction of water lost per step.
+        const EVAPORATION_RATE: f32 = 0.05;
+        const MAX_STEPS: usize = 64;
+
+        if self.bounds.area() <= 0 {
+            return;
+        }
+        for _ in 0..droplets {
+            let mut coord = Coord::new(
+                rng.gen_range(self.bounds.x_range()),
+                rng.gen_range(self.bounds.y_range()),
+            );
+            let mut water = 1.0;
+            let mut sediment = 0.0;
+
+            for _ in 0..MAX_STEPS {
+                let direction = self.steepest_descent(coord);
+                if direction == Coord::ZERO {
+                    break;
+                }
+                let next_coord = coord + direction;
+                let height = *self.get(coord).unwrap();
+                let drop = height - *self.get(next_coord).unwrap();
+                let capacity = drop * CAPACITY * water;
+
+                let cell = self.get_mut(coord).unwrap();
+                if sediment > capacity {
+                    let deposit = (sediment - capacity) * DEPOSITION_RATE;
+                    *cell += deposit;
+                    sediment -= deposit;
+                } else {
+                    // Never dig below the next cell, which would leave a pit.
+                    let erosion = ((capacity - sediment) * EROSION_RATE).min(drop);
+                    *cell -= erosion;
+                    sediment += erosion;
+                }
+
+                coord = next_coord;
+                water *= 1.0 - EVAPORATION_RATE;
+            }
+            *self.get_mut(coord).unwrap() += sediment;
+        }
+    }
+
+    /// The offset to the neighbor of steepest descent from `coord`, or
+    /// [`Coord::ZERO`] if no neighbor is lower.
+    fn steepest_descent(&self, coord: Coord) -> Coord {
+        let height = *self.get(coord).unwrap();
+        let mut steepest = (Coord::ZERO, 0.0);
+        for neighbor in Neighborhood::new(coord).iter() {
+            let neighbor_height = match self.get(neighbor) {
+                Some(&neighbor_height) => neighbor_height,
+                None => continue,
+            };
+            let offset = neighbor - coord;
+            let step_length = ((offset.x * offset.x + offset.y * offset.y) as f32).sqrt();
+            let slope = (height - neighbor_height) / step_length;
+            if slope > steepest.1 {
+                steepest = (offset, slope);
+            }
+        }
+        steepest.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::patterns::Rect;
+
+    use super::*;
+
+    /// The sum of squared height differences between orthogonal neighbors.
+    fn roughness(grid: &VecGrid<f32>) -> f32 {
+        grid.iter()
+            .flat_map(|(coord, &height)| {
+                [Coord::EAST, Coord::NORTH]
+                    .iter()
+                    .filter_map(move |&offset| grid.get(coord + offset))
+                    .map(move |&neighbor_height| (height - neighbor_height).powi(2))
+                    .collect::<Vec<_>>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn blur_single_cell_decays() {
+        let mut grid = VecGrid::<bool>::new(Rect::with_corners((-4, -4), (5, 5)));
+        grid.set((0, 0), true);
+        let mask = grid.blur_to_float(3);
+        let peak = *mask.get((0, 0)).unwrap();
+        assert!(mask.iter().all(|(_coord, &value)| value <= peak));
+        let falloff = (0..=4)
+            .map(|x| *mask.get((x, 0)).unwrap())
+            .collect::<Vec<_>>();
+        assert!(falloff.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(falloff[4], 0.0);
+        assert_eq!(mask.get((2, -2)), mask.get((-2, 2)));
+    }
+
+    #[test]
+    fn blur_solid_grid_is_one() {
+        let grid = VecGrid::with_generator(Rect::new((5, 4)), |_: Coord| true);
+        let mask = grid.blur_to_float(2);
+        assert!(mask
+            .iter()
+            .all(|(_coord, &value)| (value - 1.0).abs() < 1e-5));
+        let empty = VecGrid::<bool>::new(Rect::new((5, 4))).blur_to_float(2);
+        assert!(empty.iter().all(|(_coord, &value)| value == 0.0));
+    }
+
+    fn box_blur() -> Vec<(Coord, f32)> {
+        Neighborhood::new(Coord::ZERO)
+            .iter_moore(1)
+            .chain(std::iter::once(Coord::ZERO))
+            .map(|offset| (offset, 1.0 / 9.0))
+            .collect()
+    }
+
+    #[test]
+    fn convolve_box_blur_edge_modes() {
+        let grid = VecGrid::with_generator(Rect::new((3, 3)), |(x, y)| (x + 3 * y + 1) as u8);
+        let kernel = box_blur();
+        let expected_corners = [
+            (EdgeMode::Clamp, 21.0 / 9.0),
+            (EdgeMode::Wrap, 5.0),
+            (EdgeMode::Zero, 12.0 / 9.0),
+        ];
+        for &(edge, corner) in expected_corners.iter() {
+            let blurred = grid.convolve(&kernel, edge);
+            assert!((blurred.get((1, 1)).unwrap() - 5.0).abs() < 1e-5);
+            assert!((blurred.get((0, 0)).unwrap() - corner).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn convolve_identity_kernel() {
+        let grid =
+            VecGrid::with_generator(Rect::with_corners((-2, 1), (2, 3)), |(x, y)| (x * y) as f32);
+        let convolved = grid.convolve(&[(Coord::ZERO, 1.0)], EdgeMode::Zero);
+        assert_eq!(convolved, grid);
+    }
+
+    #[test]
+    fn erosion_smooths_terrain() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut noise = VecGrid::new(Rect::new((16, 16)));
+        for (_coord, height) in noise.iter_mut() {
+            *height = rng.gen_range(0.0..10.0);
+        }
+        let original_volume: f32 = noise.iter().map(|(_coord, &height)| height).sum();
+        let original_roughness = roughness(&noise);
+
+        noise.erode_step(500, &mut rng);
+        assert!(roughness(&noise) < original_roughness);
+        // Material is moved around rather than created or destroyed.
+        let volume: f32 = noise.iter().map(|(_coord, &height)| height).sum();
+        assert!((volume - original_volume).abs() < 0.01 * original_volume);
+    }
+
+    #[test]
+    fn approx_eq_within_epsilon() {
+        let grid = VecGrid::with_generator(Rect::new((3, 3)), |(x, y)| (x * y) as f32 / 3.0);
+        let mut other = grid.map(|&cell| cell + 0.001);
+        assert!(grid.approx_eq(&other, 0.01));
+        other.set((2, 2), 1.5);
+        assert!(!grid.approx_eq(&other, 0.01));
+        assert!(!grid.approx_eq(&grid.crop(Rect::new((2, 2))), 0.01));
+        other.set((2, 2), f32::NAN);
+        assert!(!other.approx_eq(&other, 0.01));
+    }
+
+    #[test]
+    fn ramp_gradient_points_downhill() {
+        let ramp = VecGrid::with_generator(Rect::new((4, 3)), |(x, _y)| x as f32 * 2.0);
+        let gradient = ramp.gradient();
+        assert_eq!(gradient.bounds, ramp.bounds);
+        for (coord, &direction) in gradient.iter() {
+            if coord.x == 0 {
+                assert_eq!(direction, Coord::ZERO);
+            } else {
+                assert_eq!(direction, Coord::WEST);
+            }
+        }
+    }
+
+    #[test]
+    fn single_peak_local_maxima() {
+        let mut grid = VecGrid::<i32>::new(Rect::new((5, 5)));
+        grid.set((1, 2), 4);
+        grid.set((2, 2), 10);
+        assert_eq!(grid.local_maxima(true), vec![Coord::new(2, 2)]);
+    }
+
+    #[test]
+    fn plateau_local_maxima() {
+        let grid = VecGrid::<i32>::new(Rect::new((3, 3)));
+        assert!(grid.local_maxima(true).is_empty());
+        assert_eq!(grid.local_maxima(false).len(), 9);
+    }
+
+    #[test]
+    fn single_heavy_cell_center_of_mass() {
+        let mut grid = VecGrid::<f32>::new(Rect::new((5, 5)));
+        assert_eq!(grid.center_of_mass(), None);
+        grid.set((3, 1), 10.0);
+        grid.set((0, 4), -10.0);
+        assert_eq!(grid.center_of_mass(), Some(Coord::new(3, 1)));
+    }
+
+    #[test]
+    fn balanced_center_of_mass() {
+        let mut grid = VecGrid::<f32>::new(Rect::new((5, 5)));
+        grid.set((0, 0), 1.0);
+        grid.set((4, 4), 3.0);
+        assert_eq!(grid.center_of_mass(), Some(Coord::new(3, 3)));
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    coord::Coord,
+    grid::Grid,
+    patterns::{Cluster, Connectivity, Neighborhood, Rect},
+    vecgrid::VecGrid,
+};
+
+impl<T> VecGrid<T> {
+    /// Partitions the cells satisfying `predicate` into orthogonally
+    /// connected regions.
+    ///
+    /// This is [`connected_components_with`](Self::connected_components_with)
+    /// using [`Connectivity::Orthogonal`](Connectivity::Orthogonal).
+    pub fn connected_components(&self, predicate: impl Fn(&T) -> bool) -> Vec<Cluster> {
+        self.connected_components_with(predicate, Connectivity::Orthogonal)
+    }
+
+    /// Partitions the cells satisfying `predicate` into regions of cells that
+    /// are adjacent under `connectivity`. Under
+    /// [`Connectivity::Moore`](Connectivity::Moore), a diagonal chain of cells
+    /// is a single region, but under
+    /// [`Connectivity::Orthogonal`](Connectivity::Orthogonal) each cell of the
+    /// chain is its own region.
+    ///
+    /// Regions are ordered by their first cell in row-major order.
+    pub fn connected_components_with(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Vec<Cluster> {
+        let matches = self.cells.iter().map(predicate).collect::<Vec<_>>();
+        let mut visited = vec![false; self.cells.len()];
+        let mut components = vec![];
+
+        for start_index in 0..self.cells.len() {
+            if visited[start_index] || !matches[start_index] {
+                continue;
+            }
+            visited[start_index] = true;
+            let mut component = HashSet::new();
+            let mut coords_to_search = VecDeque::new();
+            coords_to_search.push_back(self.index_to_coord(start_index));
+
+            while let Some(coord) = coords_to_search.pop_front() {
+                component.insert(coord);
+                for &offset in connectivity.offsets() {
+                    let neighbor = coord + offset;
+                    match self.coord_to_index(neighbor) {
+                        Some(index) if matches[index] && !visited[index] => {
+                            visited[index] = true;
+                            coords_to_search.push_back(neighbor);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            components.push(Cluster(component));
+        }
+        components
+    }
+
+    /// Returns the cells satisfying `a` that are orthogonally adjacent to at
+    /// least one cell satisfying `b`, such as the edge of one biome where it
+    /// meets another.
+    pub fn frontier(&self, a: impl Fn(&T) -> bool, b: impl Fn(&T) -> bool) -> Cluster {
+        Cluster::new(
+            self.iter()
+                .filter(|(coord, cell)| {
+                    a(cell)
+                        && Neighborhood::new(*coord)
+                            .iter_ortho()
+                            .any(|neighbor| self.get(neighbor).is_some_and(&b))
+                })
+                .map(|(coord, _cell)| coord),
+        )
+    }
+
+    /// Returns the passable cells whose removal would split the passable
+    /// region they belong to into multiple (4-connected) regions, in row-major
+    /// order. These are the chokepoints of a map.
+    pub fn articulation_points(&self, passable: impl Fn(&T) -> bool) -> Vec<Coord> {
+        let is_passable = |coord: Coord| self.get(coord).is_some_and(&passable);
+        // Discovery times and low-links from Tarjan's algorithm, by cell index.
+        let mut discovered = vec![None; self.cells.len()];
+        let mut low = vec![0; self.cells.len()];
+        let mut is_articulation = vec![false; self.cells.len()];
+        let mut time = 0;
+
+        for (root, _cell) in self.iter() {
+            let root_index = self.coord_to_index(root).unwrap();
+            if !is_passable(root) || discovered[root_index].is_some() {
+                continue;
+            }
+            discovered[root_index] = Some(time);
+            low[root_index] = time;
+            time += 1;
+            let mut root_children = 0;
+
+            // An explicit stack of (coord, parent, unvisited neighbors) stands
+            // in for recursion, which could overflow on large grids.
+            let mut stack = vec![(root, None, Neighborhood::new(root).into_iter_ortho())];
+            while let Some((coord, parent, neighbors)) = stack.last_mut() {
+                let coord = *coord;
+                let parent = *parent;
+                let index = self.coord_to_index(coord).unwrap();
+
+                if let Some(neighbor) = neighbors.next() {
+                    if !is_passable(neighbor) {
+                        continue;
+                    }
+                    let neighbor_index = self.coord_to_index(neighbor).unwrap();
+                    match discovered[neighbor_index] {
+                        None => {
+                            discovered[neighbor_index] = Some(time);
+                            low[neighbor_index] = time;
+                            time += 1;
+                            if coord == root {
+                                root_children += 1;
+                            }
+                            stack.push((
+                                neighbor,
+                                Some(coord),
+                                Neighborhood::new(neighbor).into_iter_ortho(),
+                            ));
+                        }
+                        Some(neighbor_time) if Some(neighbor) != parent => {
+                            low[index] = low[index].min(neighbor_time);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                if let Some(parent) = parent {
+                    let parent_index = self.coord_to_index(parent).unwrap();
+                    low[parent_index] = low[parent_index].min(low[index]);
+                    if parent != root && Some(low[index]) >= discovered[parent_index] {
+                        is_articulation[parent_index] = true;
+                    }
+                }
+            }
+
+            if root_children > 1 {
+                is_articulation[root_index] = true;
+            }
+        }
+
+        is_articulation
+            .iter()
+            .enumerate()
+            .filter(|(_index, &is_articulation)| is_articulation)
+            .map(|(index, _)| self.index_to_coord(index))
+            .collect()
+    }
+}
+
+impl VecGrid<usize> {
+    /// Builds a multiplicatively weighted Voronoi diagram, where each cell
+    /// holds the index (into `seeds`) of the seed minimizing
+    /// `distance / weight`. Seeds with a larger weight claim more territory.
+    ///
+    /// Weights are expected to be positive. Ties go to the earliest seed, and
+    /// if `seeds` is empty every cell is `0`.
+    pub fn weighted_voronoi(bounds: Rect, seeds: &[(Coord, f32)]) -> VecGrid<usize> {
+        VecGrid::with_generator(bounds, |coord: Coord| {
+            let mut closest_seed = 0;
+            let mut closest_distance = f32::INFINITY;
+            for (index, &(seed, weight)) in seeds.iter().enumerate() {
+                let delta = coord - seed;
+                let distance = ((delta.x * delta.x + delta.y * delta.y) as f32).sqrt() / weight;
+                if distance < closest_distance {
+                    closest_seed = index;
+                    closest_distance = distance;
+                }
+            }
+            closest_seed
+        })
+    }
+}
+
+impl VecGrid<bool> {
+    /// Returns the regions of empty (`false`) cells which are fully enclosed by
+    /// filled cells, meaning they can't orthogonally reach the edge of the grid.
+    pub fn enclosed_regions(&self) -> Vec<Cluster> {
+        let touches_edge = |coord: &Coord| {
+            coord.x == self.bounds.left
+                || coord.x == self.bounds.right - 1
+                || coord.y == self.bounds.top
+                || coord.y == self.bounds.bottom - 1
+        };
+        self.connected_components(|&cell| !cell)
+            .into_iter()
+            .filter(|region| !region.0.iter().any(touches_edge))
+            .collect()
+    }
+
+    /// Returns every maximal rectangle of empty (`false`) cells, meaning the
+    /// rectangles that can't be grown in any direction without covering a
+    /// filled cell or leaving the grid bounds.
+    ///
+    /// Rects are ordered by their `bottom` edge, then by their `left` edge.
+    pub fn maximal_empty_rects(&self) -> Vec<Rect> {
+        let is_empty = |x: i32, y: i32| self.get((x, y)) == Some(&false);
+        // The number of consecutive empty cells ending at the current row, per
+        // column (the "histogram" of the current row).
+        let mut heights = vec![0; self.bounds.width() as usize];
+        let mut rects = vec![];
+        let mut seen_rects = HashSet::new();
+
+        for y in self.bounds.y_range() {
+            for (height, x) in heights.iter_mut().zip(self.bounds.x_range()) {
+                *height = if is_empty(x, y) { *height + 1 } else { 0 };
+            }
+            for (column, &height) in heights.iter().enumerate() {
+                if height == 0 {
+                    continue;
+                }
+                // Widen the rect as far as the histogram stays at least as tall.
+                let mut left = column;
+                while left > 0 && heights[left - 1] >= height {
+                    left -= 1;
+                }
+                let mut right = column;
+                while right + 1 < heights.len() && heights[right + 1] >= height {
+                    right += 1;
+                }
+                let rect = Rect::with_corners(
+                    (self.bounds.left + left as i32, y - height + 1),
+                    (self.bounds.left + right as i32 + 1, y + 1),
+                );
+                // The rect isn't maximal if it could also grow into the next row.
+                let can_grow = rect.x_range().all(|x| is_empty(x, y + 1));
+                if !can_grow && seen_rects.insert(rect) {
+                    rects.push(rect);
+                }
+            }
+        }
+        rects.sort_by_key(|rect| (rect.bottom, rect.left));
+        rects
+    }
+
+    /// Returns the edges separating filled (`true`) cells from empty ones as
+    /// unit-length segments.
+    ///
+    /// Segment endpoints are in grid-line coordinates, where the cell at
+    /// `(x, y)` spans from `(x, y)` to `(x + 1, y + 1)`. Cells outside of the
+    /// grid bounds are considered empty, so filled cells along the edge of the
+    /// grid are outlined as well.
+    pub fn outline_segments(&self) -> Vec<(Coord, Coord)> {
+        let mut segments = vec![];
+        for (coord, &filled) in self.iter() {
+            if !filled {
+                continue;
+            }
+            let is_empty = |offset: Coord| !*self.get(coord + offset).unwrap_or(&false);
+            let Coord { x, y } = coord;
+
+            if is_empty(Coord::NORTH) {
+                segments.push((Coord::new(x, y + 1), Coord::new(x + 1, y + 1)));
+            }
+            if is_empty(Coord::EAST) {
+                segments.push((Coord::new(x + 1, y), Coord::new(x + 1, y + 1)));
+            }
+            if is_empty(Coord::SOUTH) {
+                segments.push((Coord::new(x, y), Coord::new(x + 1, y)));
+            }
+            if is_empty(Coord::WEST) {
+                segments.push((Coord::new(x, y), Coord::new(x, y + 1)));
+            }
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dumbbell_articulation_points() {
+        // Two 3x3 rooms joined by a 3-cell corridor along y = 1.
+        let grid =
+            VecGrid::with_generator(Rect::new((9, 3)), |(x, y)| !(3..6).contains(&x) || y == 1);
+        assert_eq!(
+            grid.articulation_points(|&cell| cell),
+            vec![
+                Coord::new(2, 1),
+                Coord::new(3, 1),
+                Coord::new(4, 1),
+                Coord::new(5, 1),
+                Coord::new(6, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn open_room_has_no_articulation_points() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |_: Coord| true);
+        assert!(grid.articulation_points(|&cell| cell).is_empty());
+    }
+
+    #[test]
+    fn stronger_seed_claims_more_cells() {
+        let seeds = [(Coord::new(2, 5), 1.0), (Coord::new(7, 5), 3.0)];
+        let voronoi = VecGrid::weighted_voronoi(Rect::new((10, 10)), &seeds);
+        let weak_cells = voronoi.iter().filter(|(_coord, &seed)| seed == 0).count();
+        let strong_cells = voronoi.iter().filter(|(_coord, &seed)| seed == 1).count();
+        assert_eq!(voronoi.get((2, 5)), Some(&0));
+        assert_eq!(voronoi.get((7, 5)), Some(&1));
+        assert!(strong_cells > weak_cells);
+    }
+
+    #[test]
+    fn two_disjoint_blobs() {
+        let mut grid = VecGrid::new(Rect::new((6, 4)));
+        grid.fill_rect(Rect::with_corners((0, 0), (2, 2)), true);
+        grid.fill_rect(Rect::with_corners((3, 1), (6, 4)), true);
+        let components = grid.connected_components(|&cell| cell);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].0.len(), 4);
+        assert_eq!(components[1].0.len(), 9);
+        assert!(components
+            .iter()
+            .flat_map(|cluster| cluster.0.iter())
+            .all(|&coord| grid.get(coord) == Some(&true)));
+    }
+
+    #[test]
+    fn fully_matching_grid_is_one_component() {
+        let grid = VecGrid::<u8>::new(Rect::with_corners((-2, -2), (3, 2)));
+        let components = grid.connected_components(|_| true);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].0.len() as i32, grid.bounds.area());
+    }
+
+    #[test]
+    fn frontier_between_halves() {
+        let grid = VecGrid::with_generator(Rect::new((6, 3)), |(x, _y)| x < 4);
+        let frontier = grid.frontier(|&left| left, |&left| !left);
+        let seam = Cluster::new((0..3).map(|y| Coord::new(3, y)));
+        assert_eq!(frontier, seam);
+        let other_side = grid.frontier(|&left| !left, |&left| left);
+        assert_eq!(other_side, Cluster::new((0..3).map(|y| Coord::new(4, y))));
+    }
+
+    #[test]
+    fn diagonal_chain_components() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x == y);
+        let moore = grid.connected_components_with(|&cell| cell, Connectivity::Moore);
+        assert_eq!(moore.len(), 1);
+        assert_eq!(moore[0].0.len(), 4);
+        let ortho = grid.connected_components_with(|&cell| cell, Connectivity::Orthogonal);
+        assert_eq!(ortho.len(), 4);
+        assert!(ortho.iter().all(|cluster| cluster.0.len() == 1));
+    }
+
+    #[test]
+    fn only_enclosed_void_is_returned() {
+        // A ring of wall enclosing a single void cell at (2, 2), with the rest
+        // of the grid empty and touching the edge.
+        let mut grid = VecGrid::new(Rect::new((6, 5)));
+        for coord in Rect::with_corners((1, 1), (4, 4)).border_iter() {
+            grid.set(coord, true);
+        }
+        let regions = grid.enclosed_regions();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].0, [Coord::new(2, 2)].iter().copied().collect());
+    }
+
+    #[test]
+    fn maximal_empty_rects_around_obstacle() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        grid.set((1, 1), true);
+        let rects = grid.maximal_empty_rects();
+        assert_eq!(rects.len(), 4);
+        assert!(rects.contains(&Rect::with_corners((0, 0), (3, 1))));
+        assert!(rects.contains(&Rect::with_corners((0, 2), (3, 3))));
+        assert!(rects.contains(&Rect::with_corners((0, 0), (1, 3))));
+        assert!(rects.contains(&Rect::with_corners((2, 0), (3, 3))));
+    }
+
+    #[test]
+    fn maximal_empty_rects_order() {
+        // Empty columns of heights 2, 3 and 1, all ending at the bottom row.
+        let grid = VecGrid::with_generator(Rect::new((3, 3)), |(x, y)| match x {
+            0 => y < 1,
+            1 => false,
+            _ => y < 2,
+        });
+        assert_eq!(
+            grid.maximal_empty_rects(),
+            vec![
+                Rect::with_corners((0, 1), (2, 3)),
+                Rect::with_corners((0, 2), (3, 3)),
+                Rect::with_corners((1, 0), (2, 3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn maximal_empty_rects_of_empty_grid() {
+        let grid = VecGrid::<bool>::new(Rect::with_corners((-2, -1), (4, 3)));
+        assert_eq!(grid.maximal_empty_rects(), vec![grid.bounds]);
+    }
+
+    #[test]
+    fn single_cell_outline() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((3, 3)));
+        grid.set((1, 1), true);
+        let segments = grid.outline_segments();
+        assert_eq!(segments.len(), 4);
+        assert!(segments.contains(&(Coord::new(1, 2), Coord::new(2, 2))));
+        assert!(segments.contains(&(Coord::new(2, 1), Coord::new(2, 2))));
+        assert!(segments.contains(&(Coord::new(1, 1), Coord::new(2, 1))));
+        assert!(segments.contains(&(Coord::new(1, 1), Coord::new(1, 2))));
+    }
+
+    #[test]
+    fn adjacent_cells_share_no_edge() {
+        let mut grid = VecGrid::<bool>::new(Rect::new((2, 1)));
+        grid.set((0, 0), true);
+        grid.set((1, 0), true);
+        assert_eq!(grid.outline_segments().len(), 6);
+    }
+}
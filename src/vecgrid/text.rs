@@ -0,0 +1,118 @@
+use std::{fmt, iter, str::FromStr};
+
+use crate::{coord::Coord, grid::GridError, patterns::Rect, vecgrid::VecGrid};
+
+impl<T: fmt::Display> VecGrid<T> {
+    /// Renders the grid as rows of values separated by `sep`, with every value
+    /// right-aligned to the width of the widest one. Each row ends in a
+    /// newline, and rows are ordered the same way as the `Display` output.
+    pub fn to_matrix_string(&self, sep: &str) -> String {
+        let values = self
+            .cells
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect::<Vec<_>>();
+        let width = values
+            .iter()
+            .map(|value| value.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut matrix = String::new();
+        for row in values.chunks(self.bounds.width().max(1) as usize) {
+            let row = row
+                .iter()
+                .map(|value| format!("{:>width$}", value, width = width))
+                .collect::<Vec<_>>();
+            matrix.push_str(&row.join(sep));
+            matrix.push('\n');
+        }
+        matrix
+    }
+}
+
+impl VecGrid<char> {
+    /// Parses a multi-line string into a grid, with each line as a row and
+    /// each character as a cell. The first line becomes `y = 0`, matching the
+    /// `Display` output.
+    ///
+    /// The grid is as wide as the longest line, and shorter lines are padded
+    /// with `fill`. An empty string is rejected with
+    /// [`GridError::InvalidFormat`](GridError::InvalidFormat).
+    pub fn from_ascii(s: &str, fill: char) -> Result<Self, GridError> {
+        let lines = s.lines().collect::<Vec<_>>();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        if width == 0 {
+            return Err(GridError::InvalidFormat);
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            let line_width = line.chars().count();
+            cells.extend(line.chars());
+            cells.extend(iter::repeat_n(fill, width - line_width));
+        }
+        VecGrid::from_parts(
+            Rect::new(Coord::new(width as i32, lines.len() as i32)),
+            cells,
+        )
+    }
+}
+
+/// Parses an ASCII-art map with [`VecGrid::from_ascii`], padding short lines
+/// with spaces.
+impl FromStr for VecGrid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VecGrid::from_ascii(s, ' ')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grid::Grid, patterns::Rect};
+
+    use super::*;
+
+    #[test]
+    fn aligned_matrix_string() {
+        let mut grid = VecGrid::<i32>::new(Rect::new((3, 2)));
+        grid.set((1, 0), 12);
+        grid.set((2, 1), -5);
+        assert_eq!(grid.to_matrix_string(" "), " 0 12  0\n 0  0 -5\n");
+        assert_eq!(grid.to_matrix_string(", "), " 0, 12,  0\n 0,  0, -5\n");
+    }
+
+    #[test]
+    fn parse_ascii_block() {
+        let grid = "abc\ndef\n".parse::<VecGrid<char>>().unwrap();
+        assert_eq!(grid.bounds, Rect::new((3, 2)));
+        assert_eq!(grid.get((0, 0)), Some(&'a'));
+        assert_eq!(grid.get((2, 0)), Some(&'c'));
+        assert_eq!(grid.get((1, 1)), Some(&'e'));
+        assert_eq!(grid.get((0, 2)), None);
+    }
+
+    #[test]
+    fn parse_ascii_pads_short_rows() {
+        let grid = VecGrid::from_ascii("#\n###\n##", '.').unwrap();
+        assert_eq!(grid.bounds, Rect::new((3, 3)));
+        assert_eq!(grid.get((1, 0)), Some(&'.'));
+        assert_eq!(grid.get((2, 2)), Some(&'.'));
+        assert_eq!(grid.get((2, 1)), Some(&'#'));
+    }
+
+    #[test]
+    fn parse_empty_ascii() {
+        assert_eq!("".parse::<VecGrid<char>>(), Err(GridError::InvalidFormat));
+        assert_eq!(
+            "\n\n".parse::<VecGrid<char>>(),
+            Err(GridError::InvalidFormat)
+        );
+    }
+}
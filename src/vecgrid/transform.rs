@@ -0,0 +1,234 @@
+use crate::{coord::Coord, grid::Grid, patterns::Rect, vecgrid::VecGrid};
+
+impl<T> VecGrid<T> {
+    /// Converts `local`, relative to the top-left corner of `self`, into the
+    /// equivalent coord relative to the top-left corner of `parent`.
+    ///
+    /// Both grids' bounds are assumed to share the same world space, as they
+    /// would after extracting `self` from `parent`.
+    pub fn to_parent_coord<U>(&self, parent: &VecGrid<U>, local: Coord) -> Coord {
+        local + self.bounds.offset() - parent.bounds.offset()
+    }
+
+    /// Converts `parent_coord`, relative to the top-left corner of `parent`,
+    /// into the equivalent coord relative to the top-left corner of `self`.
+    /// This is the inverse of [`to_parent_coord`](Self::to_parent_coord).
+    pub fn to_local_coord<U>(&self, parent: &VecGrid<U>, parent_coord: Coord) -> Coord {
+        parent_coord + parent.bounds.offset() - self.bounds.offset()
+    }
+
+    /// Builds a new grid with `dest_bounds`, where each cell is computed by
+    /// calling `sample` with `self` and the destination coord.
+    ///
+    /// The mapping between the two coordinate spaces is left entirely to
+    /// `sample`, so this can express nearest-neighbor scaling, pooling, or any
+    /// other resampling strategy.
+    pub fn resample_into(
+        &self,
+        dest_bounds: Rect,
+        sample: impl Fn(&VecGrid<T>, Coord) -> T,
+    ) -> VecGrid<T> {
+        VecGrid::with_generator(dest_bounds, |coord: Coord| sample(self, coord))
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn clockwise, as it
+    /// would appear when displayed with `top` as the first row. A W×H grid
+    /// becomes an H×W grid, with its bounds offset at the origin.
+    pub fn rotate_90(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let height = self.bounds.height();
+        self.rearranged(Rect::new((height, self.bounds.width())), |coord| {
+            Coord::new(coord.y, height - 1 - coord.x)
+        })
+    }
+
+    /// Returns a copy of the grid rotated a half turn, with its bounds offset
+    /// at the origin.
+    pub fn rotate_180(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let Coord {
+            x: width,
+            y: height,
+        } = self.bounds.dimensions();
+        self.rearranged(Rect::new((width, height)), |coord| {
+            Coord::new(width - 1 - coord.x, height - 1 - coord.y)
+        })
+    }
+
+    /// Returns a copy of the grid rotated a quarter turn counterclockwise, the
+    /// inverse of [`rotate_90`](Self::rotate_90), with its bounds offset at
+    /// the origin.
+    pub fn rotate_270(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let width = self.bounds.width();
+        self.rearranged(Rect::new((self.bounds.height(), width)), |coord| {
+            Coord::new(width - 1 - coord.y, coord.x)
+        })
+    }
+
+    /// Returns a copy of the grid with the x and y axes swapped, so a W×H grid
+    /// becomes an H×W grid, with its bounds offset at the origin.
+    pub fn transpose(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(Rect::new(self.bounds.dimensions().flip()), |coord| {
+            coord.flip()
+        })
+    }
+
+    /// Returns a copy of the grid mirrored across its vertical center line,
+    /// keeping the same bounds.
+    pub fn flip_horizontal(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let width = self.bounds.width();
+        self.rearranged(self.bounds, |coord| {
+            Coord::new(width - 1 - coord.x, coord.y)
+        })
+    }
+
+    /// Returns a copy of the grid mirrored across its horizontal center line,
+    /// keeping the same bounds.
+    pub fn flip_vertical(&self) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        let height = self.bounds.height();
+        self.rearranged(self.bounds, |coord| {
+            Coord::new(coord.x, height - 1 - coord.y)
+        })
+    }
+
+    /// Builds a grid with `bounds` by copying each cell from the coord of
+    /// `self` given by `source`, relative to the top-left corner of `self`.
+    fn rearranged(&self, bounds: Rect, source: impl Fn(Coord) -> Coord) -> VecGrid<T>
+    where
+        T: Clone,
+    {
+        VecGrid::with_generator(bounds, |coord: Coord| {
+            let source_coord = self.bounds.offset() + source(coord - bounds.offset());
+            self.get(source_coord).unwrap().clone()
+        })
+    }
+}
+
+impl VecGrid<bool> {
+    /// Returns a grid with the same bounds, where each filled (`true`) cell is
+    /// shifted by `offset`. Cells shifted out of bounds are clipped.
+    pub fn drop_shadow(&self, offset: Coord) -> VecGrid<bool> {
+        let mut shadow = VecGrid::new(self.bounds);
+        for (coord, _cell) in self.iter().filter(|(_coord, &cell)| cell) {
+            shadow.set(coord + offset, true);
+        }
+        shadow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_neighbor_downscale() {
+        let grid = VecGrid::with_generator(Rect::new((4, 4)), |(x, y)| x + y * 4);
+        let downscaled = grid.resample_into(Rect::new((2, 2)), |src, coord| {
+            *src.get(coord * Coord::new(2, 2)).unwrap()
+        });
+        assert_eq!(downscaled.bounds, Rect::new((2, 2)));
+        assert_eq!(downscaled.get((0, 0)), Some(&0));
+        assert_eq!(downscaled.get((1, 0)), Some(&2));
+        assert_eq!(downscaled.get((0, 1)), Some(&8));
+        assert_eq!(downscaled.get((1, 1)), Some(&10));
+    }
+
+    #[test]
+    fn single_cell_drop_shadow() {
+        let mut grid = VecGrid::new(Rect::new((4, 4)));
+        grid.set((1, 1), true);
+        grid.set((3, 3), true);
+        let shadow = grid.drop_shadow(Coord::new(1, 2));
+        assert_eq!(shadow.bounds, grid.bounds);
+        assert_eq!(shadow.get((2, 3)), Some(&true));
+        // The other cell's shadow falls outside of the bounds.
+        assert_eq!(shadow.iter().filter(|(_coord, &cell)| cell).count(), 1);
+    }
+
+    /// A 3x2 grid with distinct markers in each corner.
+    fn corner_markers() -> VecGrid<char> {
+        let mut grid = VecGrid::new(Rect::with_corners((5, -1), (8, 1)));
+        grid.fill('.');
+        grid.set((5, -1), 'a');
+        grid.set((7, -1), 'b');
+        grid.set((5, 0), 'c');
+        grid.set((7, 0), 'd');
+        grid
+    }
+
+    #[test]
+    fn rotate_corners() {
+        // The cells of each rotation, row by row, starting from:
+        // a.b
+        // c.d
+        let rotated = corner_markers().rotate_90();
+        assert_eq!(rotated.bounds, Rect::new((2, 3)));
+        assert_eq!(rotated.cells, "ca..db".chars().collect::<Vec<_>>());
+
+        let rotated = corner_markers().rotate_180();
+        assert_eq!(rotated.bounds, Rect::new((3, 2)));
+        assert_eq!(rotated.cells, "d.cb.a".chars().collect::<Vec<_>>());
+
+        let rotated = corner_markers().rotate_270();
+        assert_eq!(rotated.bounds, Rect::new((2, 3)));
+        assert_eq!(rotated.cells, "bd..ac".chars().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn full_rotation_is_identity() {
+        let grid = corner_markers();
+        let rotated = grid.rotate_90().rotate_90().rotate_90().rotate_90();
+        assert_eq!(rotated.cells, grid.cells);
+        assert_eq!(grid.rotate_90().rotate_270().cells, grid.cells);
+    }
+
+    #[test]
+    fn flips() {
+        let grid = corner_markers();
+        let flipped = grid.flip_horizontal();
+        assert_eq!(flipped.bounds, grid.bounds);
+        assert_eq!(flipped.cells, "b.ad.c".chars().collect::<Vec<_>>());
+        assert_eq!(flipped.flip_horizontal(), grid);
+
+        let flipped = grid.flip_vertical();
+        assert_eq!(flipped.bounds, grid.bounds);
+        assert_eq!(flipped.cells, "c.da.b".chars().collect::<Vec<_>>());
+        assert_eq!(flipped.flip_vertical(), grid);
+    }
+
+    #[test]
+    fn transpose_swaps_dimensions() {
+        let grid = VecGrid::with_generator(Rect::new((3, 2)), |(x, y)| x + y * 3);
+        let transposed = grid.transpose();
+        assert_eq!(transposed.bounds, Rect::new((2, 3)));
+        for (coord, cell) in grid.iter() {
+            assert_eq!(transposed.get(coord.flip()), Some(cell));
+        }
+    }
+
+    #[test]
+    fn parent_local_coord_round_trip() {
+        let parent = VecGrid::<()>::new(Rect::with_corners((-4, -4), (8, 8)));
+        let child = VecGrid::<()>::new(Rect::with_corners((2, 1), (6, 5)));
+        let local = Coord::new(1, 3);
+        let parent_coord = child.to_parent_coord(&parent, local);
+        assert_eq!(parent_coord, Coord::new(7, 8));
+        assert_eq!(child.to_local_coord(&parent, parent_coord), local);
+    }
+}